@@ -9,26 +9,85 @@ use tauri_plugin_opener;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // Get the state from the docker module
-    let docker_state = modules::docker::get_docker_state();
+    // Install logging first so startup is captured
+    modules::logging::initialize_logging();
 
     tauri::Builder::default()
         // Register the opener plugin for opening URLs/files
         .plugin(tauri_plugin_opener::init())
         .setup(move |app| {
             let app_handle = app.handle().clone();
+            modules::logging::initialize_log_file(&app_handle);
+            // Load the agent configuration before anything reads it
+            modules::config::initialize_config(&app_handle);
+            modules::metrics::initialize_metrics(&app_handle);
+            modules::cpu_sampler::initialize_cpu_sampler(&app_handle);
+            modules::prometheus::initialize_prometheus();
+            modules::tray::initialize_tray(&app_handle);
             // Call the initialization function from the docker module
             tauri::async_runtime::spawn(async move {
                 modules::docker::initialize_docker_monitoring(app_handle).await;
             });
             Ok(())
         })
-        .manage(docker_state)
+        // Closing the window keeps the agent running in the tray
+        .on_window_event(modules::tray::handle_window_event)
         .invoke_handler(tauri::generate_handler![
+            modules::alerts::get_active_alerts,
+            modules::alerts::get_alert_history,
+            modules::config::get_config,
+            modules::config::update_config,
+            modules::cpu_sampler::get_cpu_usage,
             modules::docker::get_docker_status,
             modules::docker::get_docker_version,
             modules::docker::subscribe_to_docker_events,
-            modules::sys_info::get_system_info
+            modules::docker::get_recent_docker_events,
+            modules::docker_compose::list_compose_projects,
+            modules::docker_compose::start_compose_project,
+            modules::docker_compose::stop_compose_project,
+            modules::docker_compose::restart_compose_project,
+            modules::docker_containers::list_docker_containers,
+            modules::docker_containers::start_docker_container,
+            modules::docker_containers::stop_docker_container,
+            modules::docker_containers::restart_docker_container,
+            modules::docker_containers::pause_docker_container,
+            modules::docker_containers::unpause_docker_container,
+            modules::docker_containers::kill_docker_container,
+            modules::docker_containers::remove_docker_container,
+            modules::docker_disk_usage::get_docker_disk_usage,
+            modules::docker_endpoints::list_docker_endpoints,
+            modules::docker_endpoints::add_docker_endpoint,
+            modules::docker_endpoints::remove_docker_endpoint,
+            modules::docker_endpoints::switch_docker_endpoint,
+            modules::docker_exec::start_exec_session,
+            modules::docker_exec::write_exec_session,
+            modules::docker_exec::resize_exec_session,
+            modules::docker_exec::close_exec_session,
+            modules::docker_exec::list_exec_sessions,
+            modules::docker_health::get_container_alerts,
+            modules::docker_health::get_container_health,
+            modules::logging::get_recent_logs,
+            modules::metrics::query_metrics,
+            modules::notifications::get_recorded_notifications,
+            modules::notifications::clear_recorded_notifications,
+            modules::docker_images::list_docker_images,
+            modules::docker_images::pull_docker_image,
+            modules::docker_images::remove_docker_image,
+            modules::docker_images::prune_docker_images,
+            modules::docker_logs::stream_container_logs,
+            modules::docker_logs::cancel_log_stream,
+            modules::docker_networks::list_docker_networks,
+            modules::docker_networks::create_docker_network,
+            modules::docker_networks::remove_docker_network,
+            modules::docker_networks::prune_docker_networks,
+            modules::docker_stats::get_container_stats,
+            modules::docker_volumes::list_docker_volumes,
+            modules::docker_volumes::create_docker_volume,
+            modules::docker_volumes::remove_docker_volume,
+            modules::docker_volumes::prune_docker_volumes,
+            modules::sys_info::get_system_info,
+            modules::webhooks::test_webhook,
+            modules::webhooks::get_webhook_deliveries
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,664 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use tauri::Emitter;
+
+use super::config;
+use super::docker::endpoint_status;
+use super::docker_endpoints;
+use super::metrics_store::MetricRow;
+use super::notifications::{self, NotificationCategory};
+use super::webhooks::{self, WebhookEvent};
+
+/// Number of fired alerts kept for `get_alert_history`
+const MAX_ALERT_HISTORY: usize = 500;
+/// Samples a subject may be missing from before its alert expires, so one
+/// failed collection does not resolve and re-fire it
+const MISSING_SAMPLES_BEFORE_EXPIRY: i64 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+impl Comparison {
+    fn label(self) -> &'static str {
+        match self {
+            Comparison::Above => "above",
+            Comparison::Below => "below",
+        }
+    }
+
+    fn breaches(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::Below => value < threshold,
+        }
+    }
+
+    /// Whether `value` is back on the safe side of the threshold by at least `hysteresis`
+    fn recovered(self, value: f64, threshold: f64, hysteresis: f64) -> bool {
+        match self {
+            Comparison::Above => value <= threshold - hysteresis,
+            Comparison::Below => value >= threshold + hysteresis,
+        }
+    }
+}
+
+/// What a rule watches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// A sampled metric such as `memory.used_percent` or `disk.free_gb[/]`.
+    /// A name without brackets also matches every labeled series of that
+    /// metric, each alerting on its own.
+    Metric {
+        metric: String,
+        comparison: Comparison,
+        threshold: f64,
+    },
+    /// The daemon on an endpoint is unreachable; the active endpoint if none is given
+    DockerNotRunning {
+        #[serde(default)]
+        endpoint: Option<String>,
+    },
+    /// A container on the active endpoint, by name, is stopped or missing.
+    /// Not evaluated while the containers cannot be listed.
+    ContainerNotRunning { container: String },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// A rule from the agent config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub severity: AlertSeverity,
+    pub condition: AlertCondition,
+    /// How long the condition must hold before the alert fires. The wait
+    /// starts over after a gap in sampling, such as while monitoring is paused.
+    #[serde(default)]
+    pub for_secs: u64,
+    /// How far a metric must recover past the threshold before the alert
+    /// resolves, so values hovering around it do not flap. Ignored for the
+    /// Docker and container conditions, which are either failing or not.
+    #[serde(default)]
+    pub hysteresis: f64,
+}
+
+impl AlertRule {
+    /// Hysteresis applied when resolving; zero for boolean conditions
+    fn effective_hysteresis(&self) -> f64 {
+        match self.condition {
+            AlertCondition::Metric { .. } => self.hysteresis,
+            _ => 0.0,
+        }
+    }
+
+    /// Rules written to a new config file
+    pub fn defaults() -> Vec<Self> {
+        let root_mount = if cfg!(target_os = "windows") {
+            "C:\\"
+        } else {
+            "/"
+        };
+        vec![
+            AlertRule {
+                id: "memory-high".to_string(),
+                name: "High memory usage".to_string(),
+                enabled: true,
+                severity: AlertSeverity::Warning,
+                condition: AlertCondition::Metric {
+                    metric: "memory.used_percent".to_string(),
+                    comparison: Comparison::Above,
+                    threshold: 90.0,
+                },
+                for_secs: 300,
+                hysteresis: 5.0,
+            },
+            AlertRule {
+                id: "disk-low".to_string(),
+                name: "Low disk space".to_string(),
+                enabled: true,
+                severity: AlertSeverity::Warning,
+                condition: AlertCondition::Metric {
+                    metric: format!("disk.free_gb[{}]", root_mount),
+                    comparison: Comparison::Below,
+                    threshold: 10.0,
+                },
+                for_secs: 0,
+                hysteresis: 1.0,
+            },
+            AlertRule {
+                id: "gpu-hot".to_string(),
+                name: "GPU temperature high".to_string(),
+                enabled: true,
+                severity: AlertSeverity::Critical,
+                condition: AlertCondition::Metric {
+                    metric: "gpu.temperature_celsius".to_string(),
+                    comparison: Comparison::Above,
+                    threshold: 85.0,
+                },
+                for_secs: 60,
+                hysteresis: 5.0,
+            },
+            AlertRule {
+                id: "docker-down".to_string(),
+                name: "Docker not running".to_string(),
+                enabled: true,
+                severity: AlertSeverity::Critical,
+                condition: AlertCondition::DockerNotRunning { endpoint: None },
+                for_secs: 120,
+                hysteresis: 0.0,
+            },
+        ]
+    }
+}
+
+/// One firing of a rule for one subject, kept after it resolves
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    /// Rule id and subject; a rule alerts at most once per subject at a time
+    pub key: String,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub severity: AlertSeverity,
+    /// Metric series, endpoint or container the alert is about
+    pub subject: String,
+    pub message: String,
+    /// Value that fired the alert, for metric rules
+    pub value: Option<f64>,
+    pub fired_at: String,
+    pub resolved_at: Option<String>,
+}
+
+/// Where a rule's subject stands against its threshold at one evaluation
+struct Observation {
+    subject: String,
+    value: f64,
+    comparison: Comparison,
+    threshold: f64,
+    message: String,
+}
+
+// Boolean conditions alert when the value is 1
+fn failing(subject: String, failing: bool, message: String) -> Observation {
+    Observation {
+        subject,
+        value: if failing { 1.0 } else { 0.0 },
+        comparison: Comparison::Above,
+        threshold: 0.5,
+        message,
+    }
+}
+
+fn observe(condition: &AlertCondition, row: &MetricRow) -> Vec<Observation> {
+    match condition {
+        AlertCondition::Metric {
+            metric,
+            comparison,
+            threshold,
+        } => row
+            .values
+            .iter()
+            .filter(|(name, _)| {
+                *name == metric
+                    || (!metric.contains('[')
+                        && name
+                            .strip_prefix(metric.as_str())
+                            .is_some_and(|rest| rest.starts_with('[')))
+            })
+            .map(|(name, summary)| Observation {
+                subject: name.clone(),
+                value: summary.mean,
+                comparison: *comparison,
+                threshold: *threshold,
+                message: format!(
+                    "{} is {:.1}, {} {}",
+                    name,
+                    summary.mean,
+                    comparison.label(),
+                    threshold
+                ),
+            })
+            .collect(),
+        AlertCondition::DockerNotRunning { endpoint } => docker_endpoints::all_states()
+            .iter()
+            .map(endpoint_status)
+            .filter(|(candidate, _)| match endpoint {
+                Some(name) => &candidate.name == name,
+                None => docker_endpoints::is_active_endpoint(&candidate.name),
+            })
+            .map(|(candidate, status)| {
+                failing(
+                    candidate.name.clone(),
+                    !status.is_running,
+                    format!("Docker is not running on {}", candidate.name),
+                )
+            })
+            .collect(),
+        AlertCondition::ContainerNotRunning { container } => {
+            // Without a container list there is nothing to check the name against
+            if !row.values.contains_key("containers.total") {
+                return Vec::new();
+            }
+            let name = container.trim_start_matches('/');
+            match row.values.get(&format!("container.running[{}]", name)) {
+                Some(summary) => vec![failing(
+                    name.to_string(),
+                    summary.mean < 1.0,
+                    format!("Container {} is not running", name),
+                )],
+                None => vec![failing(
+                    name.to_string(),
+                    true,
+                    format!("Container {} does not exist", name),
+                )],
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct AlertInstance {
+    /// When the condition started holding, while waiting for `for_secs`
+    pending_since: Option<i64>,
+    firing: Option<Alert>,
+    /// Timestamp of the latest sample the subject was observed in
+    last_seen: i64,
+}
+
+#[derive(Default)]
+struct AlertState {
+    instances: HashMap<String, AlertInstance>,
+    history: VecDeque<Alert>,
+}
+
+static ALERT_STATE: std::sync::OnceLock<Arc<Mutex<AlertState>>> = std::sync::OnceLock::new();
+
+fn get_alert_state() -> Arc<Mutex<AlertState>> {
+    ALERT_STATE
+        .get_or_init(|| Arc::new(Mutex::new(AlertState::default())))
+        .clone()
+}
+
+fn timestamp(millis: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(millis)
+        .unwrap_or_else(chrono::Utc::now)
+        .to_rfc3339()
+}
+
+impl AlertState {
+    fn resolve(&mut self, key: &str, now: i64) -> Option<Alert> {
+        let instance = self.instances.get_mut(key)?;
+        instance.pending_since = None;
+        let mut alert = instance.firing.take()?;
+        alert.resolved_at = Some(timestamp(now));
+        if let Some(entry) = self
+            .history
+            .iter_mut()
+            .rev()
+            .find(|entry| entry.key == key && entry.resolved_at.is_none())
+        {
+            entry.resolved_at = alert.resolved_at.clone();
+        }
+        Some(alert)
+    }
+
+    fn fire(&mut self, alert: Alert) {
+        if self.history.len() >= MAX_ALERT_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(alert);
+    }
+
+    /// Applies one sample's observations taken at `now`, returning the alerts
+    /// that fired and those that resolved. Subjects missing for `expiry_ms`
+    /// are resolved and forgotten.
+    fn evaluate(
+        &mut self,
+        observations: &[(AlertRule, Vec<Observation>)],
+        now: i64,
+        expiry_ms: i64,
+    ) -> (Vec<Alert>, Vec<Alert>) {
+        let mut fired = Vec::new();
+        let mut resolved = Vec::new();
+
+        // Alerts of rules that were removed or disabled resolve right away
+        let rule_ids: HashSet<&str> = observations
+            .iter()
+            .map(|(rule, _)| rule.id.as_str())
+            .collect();
+        let orphaned: Vec<String> = self
+            .instances
+            .iter()
+            .filter(|(_, instance)| {
+                instance
+                    .firing
+                    .as_ref()
+                    .is_some_and(|alert| !rule_ids.contains(alert.rule_id.as_str()))
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in orphaned {
+            resolved.extend(self.resolve(&key, now));
+            self.instances.remove(&key);
+        }
+
+        let mut observed = HashSet::new();
+        for (rule, observations) in observations {
+            for observation in observations {
+                let key = format!("{}:{}", rule.id, observation.subject);
+                observed.insert(key.clone());
+                let breaching = observation
+                    .comparison
+                    .breaches(observation.value, observation.threshold);
+                let instance = self.instances.entry(key.clone()).or_default();
+                // A gap in sampling means the condition may not have held throughout
+                if now - instance.last_seen > expiry_ms {
+                    instance.pending_since = None;
+                }
+                instance.last_seen = now;
+
+                if instance.firing.is_some() {
+                    // Already alerted; wait for a clear recovery before resolving
+                    if observation.comparison.recovered(
+                        observation.value,
+                        observation.threshold,
+                        rule.effective_hysteresis(),
+                    ) {
+                        resolved.extend(self.resolve(&key, now));
+                    }
+                    continue;
+                }
+
+                if !breaching {
+                    instance.pending_since = None;
+                    continue;
+                }
+                let since = *instance.pending_since.get_or_insert(now);
+                if now - since < rule.for_secs as i64 * 1000 {
+                    continue;
+                }
+
+                let alert = Alert {
+                    key,
+                    rule_id: rule.id.clone(),
+                    rule_name: rule.name.clone(),
+                    severity: rule.severity,
+                    subject: observation.subject.clone(),
+                    message: observation.message.clone(),
+                    value: matches!(rule.condition, AlertCondition::Metric { .. })
+                        .then_some(observation.value),
+                    fired_at: timestamp(now),
+                    resolved_at: None,
+                };
+                instance.firing = Some(alert.clone());
+                self.fire(alert.clone());
+                fired.push(alert);
+            }
+        }
+
+        // Subjects that stopped being reported, such as an unmounted disk or a
+        // removed container, expire once they have been missing for a while
+        let expired: Vec<String> = self
+            .instances
+            .iter()
+            .filter(|(key, instance)| {
+                !observed.contains(*key) && now - instance.last_seen >= expiry_ms
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired {
+            tracing::debug!(%key, "Alert subject no longer reported");
+            resolved.extend(self.resolve(&key, now));
+            self.instances.remove(&key);
+        }
+        (fired, resolved)
+    }
+}
+
+/// Evaluates every enabled rule against a new metrics sample and the current
+/// Docker status, emitting `alert-fired` and `alert-resolved` on transitions.
+/// Rules are only evaluated here, so while the metrics sampler is paused no
+/// alert fires or resolves.
+pub(crate) fn evaluate_alerts(app_handle: &tauri::AppHandle, row: &MetricRow) {
+    let agent_config = config::current_config();
+    let expiry_ms =
+        MISSING_SAMPLES_BEFORE_EXPIRY * agent_config.metrics.sample_interval_secs as i64 * 1000;
+    let rules: Vec<AlertRule> = agent_config
+        .alerts
+        .rules
+        .into_iter()
+        .filter(|rule| rule.enabled)
+        .collect();
+    // Observe before locking, since reading Docker status takes other locks
+    let observations: Vec<(AlertRule, Vec<Observation>)> = rules
+        .into_iter()
+        .map(|rule| {
+            let observations = observe(&rule.condition, row);
+            (rule, observations)
+        })
+        .collect();
+
+    let (fired, resolved) = {
+        let state = get_alert_state();
+        let mut state_guard = state.lock().unwrap();
+        state_guard.evaluate(&observations, row.timestamp, expiry_ms)
+    }; // Mutex guard is dropped here
+
+    for alert in fired {
+        tracing::warn!(key = %alert.key, message = %alert.message, "Alert fired");
+        notifications::notify_alert(&alert);
+        if let Err(error) = app_handle.emit("alert-fired", alert) {
+            tracing::warn!(%error, "Could not emit alert-fired");
+        }
+    }
+    for alert in resolved {
+        tracing::info!(key = %alert.key, "Alert resolved");
+        webhooks::publish(WebhookEvent::new(
+            NotificationCategory::Alert,
+            AlertSeverity::Info,
+            &format!("Resolved: {}", alert.rule_name),
+            &alert.message,
+        ));
+        if let Err(error) = app_handle.emit("alert-resolved", alert) {
+            tracing::warn!(%error, "Could not emit alert-resolved");
+        }
+    }
+}
+
+/// Alerts that are currently firing, most recent first
+pub(crate) fn active_alerts() -> Vec<Alert> {
+    let state = get_alert_state();
+    let state_guard = state.lock().unwrap();
+    let mut alerts: Vec<Alert> = state_guard
+        .instances
+        .values()
+        .filter_map(|instance| instance.firing.clone())
+        .collect();
+    alerts.sort_by(|a, b| b.fired_at.cmp(&a.fired_at));
+    alerts
+}
+
+/// Tauri command to get the alerts that are currently firing
+#[tauri::command]
+pub async fn get_active_alerts() -> Vec<Alert> {
+    active_alerts()
+}
+
+/// Tauri command to get fired alerts, most recent first, including resolved ones
+#[tauri::command]
+pub async fn get_alert_history(limit: Option<usize>) -> Vec<Alert> {
+    let state = get_alert_state();
+    let state_guard = state.lock().unwrap();
+    state_guard
+        .history
+        .iter()
+        .rev()
+        .take(limit.unwrap_or(MAX_ALERT_HISTORY))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL_MS: i64 = 10_000;
+    const EXPIRY_MS: i64 = MISSING_SAMPLES_BEFORE_EXPIRY * INTERVAL_MS;
+
+    fn rule(condition: AlertCondition, for_secs: u64, hysteresis: f64) -> AlertRule {
+        AlertRule {
+            id: "rule".to_string(),
+            name: "Rule".to_string(),
+            enabled: true,
+            severity: AlertSeverity::Warning,
+            condition,
+            for_secs,
+            hysteresis,
+        }
+    }
+
+    fn memory_rule(for_secs: u64, hysteresis: f64) -> AlertRule {
+        rule(
+            AlertCondition::Metric {
+                metric: "memory.used_percent".to_string(),
+                comparison: Comparison::Above,
+                threshold: 90.0,
+            },
+            for_secs,
+            hysteresis,
+        )
+    }
+
+    fn memory(value: f64) -> Observation {
+        Observation {
+            subject: "memory.used_percent".to_string(),
+            value,
+            comparison: Comparison::Above,
+            threshold: 90.0,
+            message: format!("memory.used_percent is {:.1}", value),
+        }
+    }
+
+    /// Evaluates `observations` for `rule` at `now`, returning how many alerts
+    /// fired and resolved
+    fn step(
+        state: &mut AlertState,
+        rule: &AlertRule,
+        observations: Vec<Observation>,
+        now: i64,
+    ) -> (usize, usize) {
+        let (fired, resolved) = state.evaluate(&[(rule.clone(), observations)], now, EXPIRY_MS);
+        (fired.len(), resolved.len())
+    }
+
+    #[test]
+    fn fires_once_the_condition_held_for_the_pending_window() {
+        let rule = memory_rule(30, 0.0);
+        let mut state = AlertState::default();
+
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], 0), (0, 0));
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], 20_000), (0, 0));
+        // Dropping below the threshold starts the wait over
+        assert_eq!(step(&mut state, &rule, vec![memory(50.0)], 30_000), (0, 0));
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], 40_000), (0, 0));
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], 60_000), (0, 0));
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], 70_000), (1, 0));
+        assert!(state.instances["rule:memory.used_percent"].firing.is_some());
+    }
+
+    #[test]
+    fn restarts_the_pending_window_after_a_sampling_gap() {
+        let rule = memory_rule(30, 0.0);
+        let mut state = AlertState::default();
+
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], 0), (0, 0));
+        // Monitoring was paused for longer than the expiry window
+        let resumed = EXPIRY_MS + INTERVAL_MS;
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], resumed), (0, 0));
+        assert_eq!(
+            step(&mut state, &rule, vec![memory(95.0)], resumed + 30_000),
+            (1, 0)
+        );
+    }
+
+    #[test]
+    fn resolves_only_past_the_hysteresis() {
+        let rule = memory_rule(0, 5.0);
+        let mut state = AlertState::default();
+
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], 0), (1, 0));
+        assert_eq!(step(&mut state, &rule, vec![memory(88.0)], 10_000), (0, 0));
+        assert_eq!(step(&mut state, &rule, vec![memory(85.0)], 20_000), (0, 1));
+        assert!(state.instances["rule:memory.used_percent"].firing.is_none());
+        assert!(state.history[0].resolved_at.is_some());
+    }
+
+    #[test]
+    fn does_not_fire_again_while_firing() {
+        let rule = memory_rule(0, 0.0);
+        let mut state = AlertState::default();
+
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], 0), (1, 0));
+        assert_eq!(step(&mut state, &rule, vec![memory(97.0)], 10_000), (0, 0));
+        assert_eq!(step(&mut state, &rule, vec![memory(99.0)], 20_000), (0, 0));
+        assert_eq!(state.history.len(), 1);
+    }
+
+    #[test]
+    fn expires_subjects_that_stop_being_reported() {
+        let rule = memory_rule(0, 0.0);
+        let mut state = AlertState::default();
+
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], 0), (1, 0));
+        // One missed sample is not enough to resolve
+        assert_eq!(step(&mut state, &rule, Vec::new(), INTERVAL_MS), (0, 0));
+        assert_eq!(step(&mut state, &rule, Vec::new(), EXPIRY_MS), (0, 1));
+        assert!(state.instances.is_empty());
+    }
+
+    #[test]
+    fn resolves_alerts_of_removed_rules() {
+        let rule = memory_rule(0, 0.0);
+        let mut state = AlertState::default();
+
+        assert_eq!(step(&mut state, &rule, vec![memory(95.0)], 0), (1, 0));
+        let (fired, resolved) = state.evaluate(&[], INTERVAL_MS, EXPIRY_MS);
+        assert_eq!((fired.len(), resolved.len()), (0, 1));
+    }
+
+    #[test]
+    fn ignores_hysteresis_for_boolean_conditions() {
+        let rule = rule(AlertCondition::DockerNotRunning { endpoint: None }, 0, 1.0);
+        let docker = |down: bool| {
+            vec![failing(
+                "default".to_string(),
+                down,
+                "Docker is not running on default".to_string(),
+            )]
+        };
+        let mut state = AlertState::default();
+
+        assert_eq!(step(&mut state, &rule, docker(true), 0), (1, 0));
+        assert_eq!(step(&mut state, &rule, docker(false), INTERVAL_MS), (0, 1));
+    }
+}
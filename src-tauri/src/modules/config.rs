@@ -0,0 +1,683 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+use thiserror::Error;
+use tokio::sync::watch;
+
+use super::alerts::{AlertCondition, AlertRule, Comparison};
+use super::docker;
+use super::docker_updates::UpdateSource;
+use super::logging::{self, LogLevel};
+use super::notifications::NotificationCategory;
+use super::version::UpdateChannel;
+use super::webhooks::{self, WebhookEvent, WebhookTarget};
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+#[derive(Error, Debug, Clone, Serialize)]
+pub enum ConfigError {
+    #[error("Failed to read or write the config file: {0}")]
+    Io(String),
+    #[error("Config file is not valid TOML: {0}")]
+    Parse(String),
+    #[error("Invalid config value for {field}: {reason}")]
+    Invalid { field: String, reason: String },
+}
+
+/// Timing of the Docker monitoring loop, in seconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitoringSettings {
+    pub retry_interval_secs: u64,
+    pub health_check_interval_secs: u64,
+    pub update_check_interval_secs: u64,
+    pub connection_timeout_secs: u64,
+    pub max_retries: u32,
+    pub stats_interval_secs: u64,
+    /// A container restarting more than this many times within the window raises an alert
+    pub restart_alert_threshold: u32,
+    pub restart_alert_window_secs: u64,
+}
+
+impl Default for MonitoringSettings {
+    fn default() -> Self {
+        Self {
+            retry_interval_secs: 1,
+            health_check_interval_secs: 30,
+            update_check_interval_secs: 3600, // 1 hour
+            connection_timeout_secs: 5,
+            max_retries: 3,
+            stats_interval_secs: 2,
+            restart_alert_threshold: 3,
+            restart_alert_window_secs: 600, // 10 minutes
+        }
+    }
+}
+
+/// Where and how often to look for Docker updates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateSettings {
+    pub engine_source: UpdateSource,
+    pub desktop_source: UpdateSource,
+    pub channel: UpdateChannel,
+}
+
+impl Default for UpdateSettings {
+    fn default() -> Self {
+        Self {
+            engine_source: UpdateSource::default_engine(),
+            desktop_source: UpdateSource::default_desktop(),
+            channel: UpdateChannel::Stable,
+        }
+    }
+}
+
+/// HTTP client used for update checks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpSettings {
+    pub timeout_secs: u64,
+    pub user_agent: String,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 10,
+            user_agent: "Desktop-Agent/1.0".to_string(),
+        }
+    }
+}
+
+/// Historical metrics sampling and how long each resolution is kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsSettings {
+    pub sample_interval_secs: u64,
+    pub raw_retention_hours: u64,
+    pub minute_retention_days: u64,
+    pub hour_retention_days: u64,
+    /// How often live CPU usage, frequency and load are refreshed
+    pub cpu_sample_interval_secs: u64,
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        Self {
+            sample_interval_secs: 10,
+            raw_retention_hours: 24,
+            minute_retention_days: 7,
+            hour_retention_days: 365,
+            cpu_sample_interval_secs: 2,
+        }
+    }
+}
+
+/// Local HTTP listener serving `/metrics` for Prometheus
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrometheusSettings {
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+}
+
+impl Default for PrometheusSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "127.0.0.1".to_string(),
+            port: 9464,
+        }
+    }
+}
+
+/// Alert rules evaluated on every metrics sample
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertSettings {
+    pub rules: Vec<AlertRule>,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        Self {
+            rules: AlertRule::defaults(),
+        }
+    }
+}
+
+/// Desktop notifications for Docker state changes, updates, crashes and alerts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    /// Categories that never produce a notification
+    pub muted: Vec<NotificationCategory>,
+    /// Minimum time between two notifications about the same thing
+    pub cooldown_secs: u64,
+    /// Limit across all categories, so a burst of failures stays readable
+    pub max_per_minute: u32,
+    /// Record notifications for `get_recorded_notifications` instead of showing them
+    pub test_mode: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            muted: Vec::new(),
+            cooldown_secs: 300,
+            max_per_minute: 5,
+            test_mode: false,
+        }
+    }
+}
+
+/// HTTP webhooks receiving alerts and Docker transitions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookSettings {
+    pub targets: Vec<WebhookTarget>,
+    /// Attempts per event and webhook, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each further retry
+    pub initial_backoff_secs: u64,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            targets: Vec::new(),
+            max_attempts: 4,
+            initial_backoff_secs: 2,
+        }
+    }
+}
+
+/// Tray icon behavior
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TraySettings {
+    /// Hide the window instead of quitting when it is closed
+    pub close_to_tray: bool,
+    /// Container, by name or id, that the tray menu can restart
+    pub pinned_container: Option<String>,
+}
+
+impl Default for TraySettings {
+    fn default() -> Self {
+        Self {
+            close_to_tray: true,
+            pinned_container: None,
+        }
+    }
+}
+
+/// Verbosity of the agent's own log
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingSettings {
+    pub level: LogLevel,
+}
+
+/// Agent configuration stored as `config.toml` in the app config directory.
+/// Missing sections and fields fall back to their defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentConfig {
+    pub monitoring: MonitoringSettings,
+    pub updates: UpdateSettings,
+    pub http: HttpSettings,
+    pub logging: LoggingSettings,
+    pub metrics: MetricsSettings,
+    pub prometheus: PrometheusSettings,
+    pub alerts: AlertSettings,
+    pub notifications: NotificationSettings,
+    pub tray: TraySettings,
+    pub webhooks: WebhookSettings,
+}
+
+fn invalid(field: &str, reason: &str) -> ConfigError {
+    ConfigError::Invalid {
+        field: field.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+fn check_range(field: &str, value: u64, min: u64, max: u64) -> Result<(), ConfigError> {
+    if value < min || value > max {
+        return Err(invalid(
+            field,
+            &format!("must be between {} and {}", min, max),
+        ));
+    }
+    Ok(())
+}
+
+fn check_url(field: &str, url: &str) -> Result<(), ConfigError> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(invalid(field, "must be an http:// or https:// URL"))
+    }
+}
+
+fn check_alert_rule(index: usize, rule: &AlertRule) -> Result<(), ConfigError> {
+    let field = format!("alerts.rules[{}]", index);
+    if rule.id.trim().is_empty() || rule.name.trim().is_empty() {
+        return Err(invalid(&field, "id and name must not be empty"));
+    }
+    check_range(&format!("{}.for_secs", field), rule.for_secs, 0, 24 * 3600)?;
+    if !rule.hysteresis.is_finite() || rule.hysteresis < 0.0 {
+        return Err(invalid(&field, "hysteresis must be zero or positive"));
+    }
+    match &rule.condition {
+        AlertCondition::Metric {
+            metric,
+            comparison,
+            threshold,
+        } => {
+            if metric.trim().is_empty() {
+                return Err(invalid(&field, "metric must not be empty"));
+            }
+            if !threshold.is_finite() {
+                return Err(invalid(&field, "threshold must be a number"));
+            }
+            // Sampled metrics are never negative, and percentages other than
+            // container CPU (which counts every core) never pass 100
+            let series = metric.split('[').next().unwrap_or(metric);
+            let max = if series.ends_with("_percent") && series != "container.cpu_percent" {
+                100.0
+            } else {
+                f64::INFINITY
+            };
+            let recovery = match comparison {
+                Comparison::Above => threshold - rule.hysteresis,
+                Comparison::Below => threshold + rule.hysteresis,
+            };
+            if rule.hysteresis > 0.0 && !(0.0..=max).contains(&recovery) {
+                return Err(invalid(
+                    &field,
+                    "hysteresis puts the recovery point outside the metric's range, so the alert could never resolve",
+                ));
+            }
+        }
+        AlertCondition::DockerNotRunning { .. } => {}
+        AlertCondition::ContainerNotRunning { container } => {
+            if container.trim().is_empty() {
+                return Err(invalid(&field, "container must not be empty"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_webhook(index: usize, target: &WebhookTarget) -> Result<(), ConfigError> {
+    let field = format!("webhooks.targets[{}]", index);
+    if target.name.trim().is_empty() {
+        return Err(invalid(&field, "name must not be empty"));
+    }
+    check_url(&format!("{}.url", field), &target.url)?;
+    if target
+        .secret
+        .as_ref()
+        .is_some_and(|secret| secret.is_empty())
+    {
+        return Err(invalid(&format!("{}.secret", field), "must not be empty"));
+    }
+    if let Some(template) = &target.template {
+        let sample = WebhookEvent::new(
+            NotificationCategory::Alert,
+            Default::default(),
+            "title",
+            "message",
+        );
+        if let Err(error) = webhooks::render_template(template, &sample) {
+            return Err(invalid(
+                &format!("{}.template", field),
+                &format!("must be JSON once placeholders are filled in: {}", error),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_update_source(field: &str, source: &UpdateSource) -> Result<(), ConfigError> {
+    match source {
+        UpdateSource::DockerHub {
+            base_url,
+            repository,
+        }
+        | UpdateSource::GithubReleases {
+            api_url: base_url,
+            repository,
+        } => {
+            check_url(field, base_url)?;
+            if repository.split('/').count() != 2 {
+                return Err(invalid(field, "repository must look like owner/name"));
+            }
+            Ok(())
+        }
+        UpdateSource::JsonFeed { url } => check_url(field, url),
+        UpdateSource::DesktopAppcast { base_url } => check_url(field, base_url),
+    }
+}
+
+impl AgentConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let m = &self.monitoring;
+        check_range(
+            "monitoring.retry_interval_secs",
+            m.retry_interval_secs,
+            1,
+            300,
+        )?;
+        check_range(
+            "monitoring.health_check_interval_secs",
+            m.health_check_interval_secs,
+            1,
+            3600,
+        )?;
+        check_range(
+            "monitoring.update_check_interval_secs",
+            m.update_check_interval_secs,
+            60,
+            7 * 24 * 3600,
+        )?;
+        check_range(
+            "monitoring.connection_timeout_secs",
+            m.connection_timeout_secs,
+            1,
+            120,
+        )?;
+        check_range("monitoring.max_retries", m.max_retries.into(), 1, 100)?;
+        check_range(
+            "monitoring.stats_interval_secs",
+            m.stats_interval_secs,
+            1,
+            300,
+        )?;
+        check_range(
+            "monitoring.restart_alert_threshold",
+            m.restart_alert_threshold.into(),
+            1,
+            1000,
+        )?;
+        check_range(
+            "monitoring.restart_alert_window_secs",
+            m.restart_alert_window_secs,
+            10,
+            24 * 3600,
+        )?;
+
+        check_update_source("updates.engine_source", &self.updates.engine_source)?;
+        check_update_source("updates.desktop_source", &self.updates.desktop_source)?;
+
+        let metrics = &self.metrics;
+        check_range(
+            "metrics.sample_interval_secs",
+            metrics.sample_interval_secs,
+            1,
+            3600,
+        )?;
+        check_range(
+            "metrics.cpu_sample_interval_secs",
+            metrics.cpu_sample_interval_secs,
+            1,
+            60,
+        )?;
+        check_range(
+            "metrics.raw_retention_hours",
+            metrics.raw_retention_hours,
+            1,
+            7 * 24,
+        )?;
+        check_range(
+            "metrics.minute_retention_days",
+            metrics.minute_retention_days,
+            1,
+            90,
+        )?;
+        check_range(
+            "metrics.hour_retention_days",
+            metrics.hour_retention_days,
+            1,
+            5 * 365,
+        )?;
+
+        if self
+            .prometheus
+            .bind_address
+            .parse::<std::net::IpAddr>()
+            .is_err()
+        {
+            return Err(invalid(
+                "prometheus.bind_address",
+                "must be an IP address such as 127.0.0.1",
+            ));
+        }
+        check_range("prometheus.port", self.prometheus.port.into(), 1, 65535)?;
+
+        let mut rule_ids = std::collections::HashSet::new();
+        for (index, rule) in self.alerts.rules.iter().enumerate() {
+            check_alert_rule(index, rule)?;
+            if !rule_ids.insert(rule.id.as_str()) {
+                return Err(invalid(
+                    &format!("alerts.rules[{}]", index),
+                    "rule ids must be unique",
+                ));
+            }
+        }
+
+        check_range(
+            "notifications.cooldown_secs",
+            self.notifications.cooldown_secs,
+            0,
+            24 * 3600,
+        )?;
+        check_range(
+            "notifications.max_per_minute",
+            self.notifications.max_per_minute.into(),
+            1,
+            60,
+        )?;
+
+        check_range(
+            "webhooks.max_attempts",
+            self.webhooks.max_attempts.into(),
+            1,
+            10,
+        )?;
+        check_range(
+            "webhooks.initial_backoff_secs",
+            self.webhooks.initial_backoff_secs,
+            1,
+            300,
+        )?;
+        let mut webhook_names = std::collections::HashSet::new();
+        for (index, target) in self.webhooks.targets.iter().enumerate() {
+            check_webhook(index, target)?;
+            if !webhook_names.insert(target.name.as_str()) {
+                return Err(invalid(
+                    &format!("webhooks.targets[{}]", index),
+                    "webhook names must be unique",
+                ));
+            }
+        }
+
+        if self
+            .tray
+            .pinned_container
+            .as_ref()
+            .is_some_and(|name| name.trim().is_empty())
+        {
+            return Err(invalid("tray.pinned_container", "must not be empty"));
+        }
+
+        check_range("http.timeout_secs", self.http.timeout_secs, 1, 300)?;
+        if self.http.user_agent.trim().is_empty() {
+            return Err(invalid("http.user_agent", "must not be empty"));
+        }
+        Ok(())
+    }
+
+    fn load(path: &PathBuf) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(e.to_string()))?;
+        let config: Self =
+            toml::from_str(&contents).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn save(&self, path: &PathBuf) -> Result<(), ConfigError> {
+        let contents =
+            toml::to_string_pretty(self).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| ConfigError::Io(e.to_string()))?;
+        }
+        // Write a temporary file first so a crash cannot leave a truncated config
+        let tmp_path = path.with_extension("toml.tmp");
+        std::fs::write(&tmp_path, contents).map_err(|e| ConfigError::Io(e.to_string()))?;
+        std::fs::rename(&tmp_path, path).map_err(|e| ConfigError::Io(e.to_string()))
+    }
+}
+
+struct ConfigState {
+    path: Option<PathBuf>,
+    updates: watch::Sender<AgentConfig>,
+}
+
+static CONFIG_STATE: std::sync::OnceLock<Arc<Mutex<ConfigState>>> = std::sync::OnceLock::new();
+
+fn get_config_state() -> Arc<Mutex<ConfigState>> {
+    CONFIG_STATE
+        .get_or_init(|| {
+            let (updates, _) = watch::channel(AgentConfig::default());
+            Arc::new(Mutex::new(ConfigState {
+                path: None,
+                updates,
+            }))
+        })
+        .clone()
+}
+
+/// The configuration currently in effect
+pub fn current_config() -> AgentConfig {
+    let state = get_config_state();
+    let state_guard = state.lock().unwrap();
+    let config = state_guard.updates.borrow().clone();
+    config
+}
+
+/// Receives the new configuration every time it is updated
+pub(crate) fn subscribe_to_config() -> watch::Receiver<AgentConfig> {
+    let state = get_config_state();
+    let state_guard = state.lock().unwrap();
+    state_guard.updates.subscribe()
+}
+
+/// Loads `config.toml` from the app config directory, writing the defaults
+/// if it does not exist yet. An unreadable or invalid file is left untouched
+/// and the defaults are used.
+pub fn initialize_config(app_handle: &tauri::AppHandle) {
+    let path = match app_handle.path().app_config_dir() {
+        Ok(dir) => dir.join(CONFIG_FILE_NAME),
+        Err(_) => return,
+    };
+
+    let config = if path.exists() {
+        AgentConfig::load(&path).unwrap_or_else(|error| {
+            tracing::warn!(path = %path.display(), %error, "Ignoring config file, using defaults");
+            AgentConfig::default()
+        })
+    } else {
+        let config = AgentConfig::default();
+        if let Err(error) = config.save(&path) {
+            tracing::warn!(path = %path.display(), %error, "Could not write default config file");
+        }
+        config
+    };
+    logging::set_log_level(config.logging.level);
+
+    let state = get_config_state();
+    let mut state_guard = state.lock().unwrap();
+    state_guard.path = Some(path);
+    state_guard.updates.send_replace(config);
+}
+
+#[tauri::command]
+pub async fn get_config() -> Result<AgentConfig, ConfigError> {
+    Ok(current_config())
+}
+
+/// Tauri command to validate, save and apply a new configuration. Running
+/// monitoring loops pick up interval changes without a restart.
+#[tauri::command]
+pub async fn update_config(
+    app_handle: tauri::AppHandle,
+    config: AgentConfig,
+) -> Result<AgentConfig, ConfigError> {
+    config.validate()?;
+
+    let path = {
+        let state = get_config_state();
+        let state_guard = state.lock().unwrap();
+        state_guard.path.clone()
+    };
+    if let Some(path) = &path {
+        config.save(path)?;
+    }
+
+    // Update every endpoint's state before waking the loops that read it
+    docker::apply_config(&config);
+    logging::set_log_level(config.logging.level);
+    {
+        let state = get_config_state();
+        let state_guard = state.lock().unwrap();
+        state_guard.updates.send_replace(config.clone());
+    }
+
+    tracing::info!("Configuration updated");
+    if let Err(error) = app_handle.emit("config-updated", config.clone()) {
+        tracing::warn!(%error, "Could not emit config-updated");
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_hysteresis(id: &str, hysteresis: f64) -> AlertRule {
+        let mut rule = AlertRule::defaults()
+            .into_iter()
+            .find(|rule| rule.id == id)
+            .unwrap();
+        rule.hysteresis = hysteresis;
+        rule
+    }
+
+    #[test]
+    fn rejects_hysteresis_the_metric_cannot_recover_past() {
+        // memory.used_percent above 90 and disk.free_gb below 10
+        assert!(check_alert_rule(0, &with_hysteresis("memory-high", 10.0)).is_ok());
+        assert!(check_alert_rule(0, &with_hysteresis("memory-high", 95.0)).is_err());
+        assert!(check_alert_rule(0, &with_hysteresis("disk-low", 500.0)).is_ok());
+
+        let mut used = with_hysteresis("memory-high", 5.0);
+        used.condition = AlertCondition::Metric {
+            metric: "disk.used_percent[/]".to_string(),
+            comparison: Comparison::Below,
+            threshold: 98.0,
+        };
+        assert!(check_alert_rule(0, &used).is_err());
+    }
+
+    #[test]
+    fn accepts_any_hysteresis_for_boolean_conditions() {
+        assert!(check_alert_rule(0, &with_hysteresis("docker-down", 1.0)).is_ok());
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sysinfo::{CpuRefreshKind, RefreshKind, System};
+use tauri::Emitter;
+
+use super::config;
+use super::docker;
+use super::sys_info::{get_cpu_info, CpuInfo, SysInfoError};
+
+static CPU_SNAPSHOT: std::sync::OnceLock<Arc<Mutex<Option<CpuInfo>>>> = std::sync::OnceLock::new();
+
+fn get_cpu_snapshot() -> Arc<Mutex<Option<CpuInfo>>> {
+    CPU_SNAPSHOT
+        .get_or_init(|| Arc::new(Mutex::new(None)))
+        .clone()
+}
+
+/// CPU information from the latest sample, once one has been taken
+pub(crate) fn latest_cpu_info() -> Option<CpuInfo> {
+    let snapshot = get_cpu_snapshot();
+    let snapshot_guard = snapshot.lock().unwrap();
+    snapshot_guard.clone()
+}
+
+fn sample_interval(seconds: u64) -> tokio::time::Interval {
+    let period = Duration::from_secs(seconds);
+    // Usage is measured between two refreshes, so skip the immediate first tick
+    tokio::time::interval_at(tokio::time::Instant::now() + period, period)
+}
+
+// Refresh a long-lived System so usage is measured over each interval, and
+// publish the result to the frontend
+async fn cpu_sampler_loop(app_handle: tauri::AppHandle) {
+    let mut interval_secs = config::current_config().metrics.cpu_sample_interval_secs;
+    let mut interval = sample_interval(interval_secs);
+    let mut config_updates = config::subscribe_to_config();
+
+    let mut system =
+        System::new_with_specifics(RefreshKind::nothing().with_cpu(CpuRefreshKind::everything()));
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if docker::is_monitoring_paused() {
+                    continue;
+                }
+                let sampled = tauri::async_runtime::spawn_blocking(move || {
+                    system.refresh_cpu_all();
+                    let info = get_cpu_info(&system, true);
+                    (system, info)
+                })
+                .await;
+                match sampled {
+                    Ok((returned, Ok(info))) => {
+                        system = returned;
+                        {
+                            let snapshot = get_cpu_snapshot();
+                            *snapshot.lock().unwrap() = Some(info.clone());
+                        } // Mutex guard is dropped here
+                        if let Err(error) = app_handle.emit("cpu-info-updated", info) {
+                            tracing::warn!(%error, "Could not emit cpu-info-updated");
+                        }
+                    }
+                    Ok((returned, Err(error))) => {
+                        system = returned;
+                        tracing::debug!(%error, "CPU sample failed");
+                    }
+                    Err(error) => {
+                        tracing::error!(%error, "CPU sampling panicked");
+                        system = System::new_with_specifics(
+                            RefreshKind::nothing().with_cpu(CpuRefreshKind::everything()),
+                        );
+                    }
+                }
+            }
+            Ok(()) = config_updates.changed() => {
+                let new_interval_secs = config_updates.borrow_and_update().metrics.cpu_sample_interval_secs;
+                if new_interval_secs != interval_secs {
+                    interval = sample_interval(new_interval_secs);
+                    interval_secs = new_interval_secs;
+                }
+            }
+        }
+    }
+}
+
+/// Starts sampling CPU usage, frequency and load in the background
+pub fn initialize_cpu_sampler(app_handle: &tauri::AppHandle) {
+    tauri::async_runtime::spawn(cpu_sampler_loop(app_handle.clone()));
+}
+
+/// Tauri command to get live CPU information. Usage is `None` until the
+/// first sample has been taken.
+#[tauri::command]
+pub async fn get_cpu_usage() -> Result<CpuInfo, SysInfoError> {
+    if let Some(info) = latest_cpu_info() {
+        return Ok(info);
+    }
+    tauri::async_runtime::spawn_blocking(|| {
+        let system = System::new_with_specifics(
+            RefreshKind::nothing().with_cpu(CpuRefreshKind::everything()),
+        );
+        get_cpu_info(&system, false)
+    })
+    .await
+    .map_err(|e| SysInfoError::System(e.to_string()))?
+}
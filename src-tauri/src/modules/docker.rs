@@ -5,13 +5,27 @@ use chrono;
 use futures_util::StreamExt;
 use reqwest;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
 use tauri::Emitter;
 use thiserror::Error;
 use tokio::sync::broadcast;
-
-#[derive(Error, Debug, Clone)]
+use tracing::Instrument;
+
+use super::config::{self, AgentConfig, HttpSettings};
+use super::docker_desktop::DesktopEnvironment;
+use super::docker_endpoints::{self, DockerEndpoint, EngineFlavor};
+use super::docker_exec::{self, ExecSession};
+use super::docker_health;
+use super::docker_stats;
+use super::docker_updates::{self, UpdateInfo, UpdateSource};
+use super::notifications;
+use super::version::UpdateChannel;
+
+#[derive(Error, Debug, Clone, Serialize)]
 pub enum DockerError {
     #[error("Docker is not running")]
     NotRunning,
@@ -25,15 +39,58 @@ pub enum DockerError {
     Restarting,
     #[error("Failed to connect to Docker: {0}")]
     ConnectionFailed(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("Docker request failed: {0}")]
+    RequestFailed(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+}
+
+// Map bollard errors to the cases the frontend can act on
+impl From<bollard::errors::Error> for DockerError {
+    fn from(error: bollard::errors::Error) -> Self {
+        match error {
+            bollard::errors::Error::DockerResponseServerError {
+                status_code,
+                message,
+            } => match status_code {
+                404 => DockerError::NotFound(message),
+                409 => DockerError::Conflict(message),
+                401 | 403 => DockerError::PermissionDenied(message),
+                _ => DockerError::RequestFailed(message),
+            },
+            bollard::errors::Error::IOError { err }
+                if err.kind() == std::io::ErrorKind::PermissionDenied =>
+            {
+                DockerError::PermissionDenied(err.to_string())
+            }
+            bollard::errors::Error::RequestTimeoutError => DockerError::Timeout,
+            other => DockerError::RequestFailed(other.to_string()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockerStatus {
+    // Name of the endpoint this status describes
+    pub endpoint: String,
     pub is_running: bool,
+    // Socket or address that answered, which may differ from the endpoint's
+    // configured host after local socket discovery
+    pub host: Option<String>,
+    pub engine_flavor: Option<EngineFlavor>,
     pub engine_version: Option<DockerVersion>,
     pub desktop_version: Option<String>,
     pub engine_update_available: Option<bool>,
     pub desktop_update_available: Option<bool>,
+    // Latest versions and release notes behind the two flags above
+    pub engine_update: Option<UpdateInfo>,
+    pub desktop_update: Option<UpdateInfo>,
     pub error: Option<String>,
     pub container_count: Option<i32>,
     pub last_checked: Option<String>,
@@ -47,38 +104,115 @@ pub struct DockerVersion {
     pub arch: String,
 }
 
+// A decoded Docker daemon event, emitted as `docker-event`
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct UpdateInfo {
-    current_version: String,
-    latest_version: String,
-    update_available: bool,
-    release_notes: Option<String>,
+pub struct DockerEvent {
+    pub event_type: String,
+    pub action: String,
+    pub actor_id: Option<String>,
+    pub attributes: HashMap<String, String>,
+    pub scope: Option<String>,
+    pub timestamp: String,
+}
+
+impl From<bollard::models::EventMessage> for DockerEvent {
+    fn from(event: bollard::models::EventMessage) -> Self {
+        let (actor_id, attributes) = match event.actor {
+            Some(actor) => (actor.id, actor.attributes.unwrap_or_default()),
+            None => (None, HashMap::new()),
+        };
+
+        // Prefer the nanosecond timestamp when the daemon provides it
+        let timestamp = event
+            .time_nano
+            .map(chrono::DateTime::from_timestamp_nanos)
+            .or_else(|| {
+                event
+                    .time
+                    .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+            })
+            .unwrap_or_else(chrono::Utc::now);
+
+        Self {
+            event_type: event.typ.map(|t| t.to_string()).unwrap_or_default(),
+            action: event.action.unwrap_or_default(),
+            actor_id,
+            attributes,
+            scope: event.scope.map(|s| s.to_string()),
+            timestamp: timestamp.to_rfc3339(),
+        }
+    }
+}
+
+impl DockerEvent {
+    // Actions such as "health_status: healthy" carry a detail after the colon
+    pub fn base_action(&self) -> &str {
+        self.action
+            .split_once(':')
+            .map(|(base, _)| base)
+            .unwrap_or(&self.action)
+    }
 }
 
 // Configuration for the monitoring system
 #[derive(Debug, Clone)]
-struct MonitoringConfig {
+pub(crate) struct MonitoringConfig {
     retry_interval: Duration,
     health_check_interval: Duration,
     update_check_interval: Duration,
     connection_timeout: Duration,
     max_retries: u32,
+    stats_interval: Duration,
+    // A container restarting more than this many times within the window raises an alert
+    pub(crate) restart_alert_threshold: u32,
+    pub(crate) restart_alert_window: Duration,
+    // Where to look for new engine and Docker Desktop releases
+    pub(crate) engine_update_source: UpdateSource,
+    pub(crate) desktop_update_source: UpdateSource,
+    pub(crate) update_channel: UpdateChannel,
 }
 
+// Defaults live in AgentConfig so the config file and the agent agree on them
 impl Default for MonitoringConfig {
     fn default() -> Self {
+        Self::from(&AgentConfig::default())
+    }
+}
+
+impl From<&AgentConfig> for MonitoringConfig {
+    fn from(config: &AgentConfig) -> Self {
+        let monitoring = &config.monitoring;
         Self {
-            retry_interval: Duration::from_secs(1),
-            health_check_interval: Duration::from_secs(30),
-            update_check_interval: Duration::from_secs(3600), // 1 hour
-            connection_timeout: Duration::from_secs(5),
-            max_retries: 3,
+            retry_interval: Duration::from_secs(monitoring.retry_interval_secs),
+            health_check_interval: Duration::from_secs(monitoring.health_check_interval_secs),
+            update_check_interval: Duration::from_secs(monitoring.update_check_interval_secs),
+            connection_timeout: Duration::from_secs(monitoring.connection_timeout_secs),
+            max_retries: monitoring.max_retries,
+            stats_interval: Duration::from_secs(monitoring.stats_interval_secs),
+            restart_alert_threshold: monitoring.restart_alert_threshold,
+            restart_alert_window: Duration::from_secs(monitoring.restart_alert_window_secs),
+            engine_update_source: config.updates.engine_source.clone(),
+            desktop_update_source: config.updates.desktop_source.clone(),
+            update_channel: config.updates.channel,
         }
     }
 }
 
+// HTTP client used for update checks
+fn build_http_client(settings: &HttpSettings) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(settings.timeout_secs))
+        .user_agent(settings.user_agent.as_str())
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+// Maximum number of events kept for `get_recent_docker_events`
+const MAX_RECENT_EVENTS: usize = 500;
+
 // Shared state for Docker connection and status
 pub struct DockerState {
+    endpoint: DockerEndpoint,
     docker: Option<Docker>,
     status: DockerStatus,
     event_sender: broadcast::Sender<DockerStatus>,
@@ -86,252 +220,180 @@ pub struct DockerState {
     retry_count: u32,
     last_update_check: Option<std::time::Instant>,
     http_client: reqwest::Client,
+    recent_events: VecDeque<DockerEvent>,
+    // Task following this endpoint's event stream; there is only ever one
+    event_task: Option<JoinHandle<()>>,
+    pub(crate) exec_sessions: HashMap<String, ExecSession>,
 }
 
 impl DockerState {
-    fn new() -> Self {
+    pub(crate) fn new(endpoint: DockerEndpoint) -> Self {
         let (event_sender, _) = broadcast::channel(100);
-        let http_client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(10))
-            .user_agent("Desktop-Agent/1.0")
-            .build()
-            .expect("Failed to create HTTP client");
+        let agent_config = config::current_config();
 
         Self {
             docker: None,
             status: DockerStatus {
+                endpoint: endpoint.name.clone(),
                 is_running: false,
+                host: None,
+                engine_flavor: None,
                 engine_version: None,
                 desktop_version: None,
                 engine_update_available: None,
                 desktop_update_available: None,
+                engine_update: None,
+                desktop_update: None,
                 error: Some("Initializing...".to_string()),
                 container_count: None,
                 last_checked: None,
             },
             event_sender,
-            config: MonitoringConfig::default(),
+            config: MonitoringConfig::from(&agent_config),
             retry_count: 0,
             last_update_check: None,
-            http_client,
+            http_client: build_http_client(&agent_config.http),
+            recent_events: VecDeque::with_capacity(MAX_RECENT_EVENTS),
+            event_task: None,
+            exec_sessions: HashMap::new(),
+            endpoint,
         }
     }
 }
 
-// Global state
-static DOCKER_STATE: std::sync::OnceLock<Arc<Mutex<DockerState>>> = std::sync::OnceLock::new();
-
+// State of the active endpoint; every endpoint has its own DockerState
 pub fn get_docker_state() -> Arc<Mutex<DockerState>> {
-    DOCKER_STATE
-        .get_or_init(|| Arc::new(Mutex::new(DockerState::new())))
-        .clone()
+    docker_endpoints::get_active_state()
 }
 
-// Professional Docker update checking using official APIs
-async fn check_docker_engine_update_available(
-    current_version: &str,
-    client: &reqwest::Client,
-) -> Option<bool> {
-    // Try Docker Hub API first (official source)
-    let response = client
-        .get("https://hub.docker.com/v2/repositories/library/docker/tags/")
-        .query(&[
-            ("page_size", "1"),
-            ("ordering", "last_updated"),
-            ("name", "stable"),
-        ])
-        .send()
-        .await
-        .ok()?;
-
-    if response.status().is_success() {
-        if let Ok(json) = response.json::<serde_json::Value>().await {
-            if let Some(results) = json["results"].as_array() {
-                if let Some(latest) = results.first() {
-                    if let Some(latest_version) = latest["name"].as_str() {
-                        return Some(compare_versions(current_version, latest_version));
-                    }
-                }
-            }
-        }
-    }
-
-    None
+// Get an endpoint's definition together with its current status
+pub(crate) fn endpoint_status(state: &Arc<Mutex<DockerState>>) -> (DockerEndpoint, DockerStatus) {
+    let state_guard = state.lock().unwrap();
+    (state_guard.endpoint.clone(), state_guard.status.clone())
 }
 
-// Professional Docker Desktop update checking using official sources
-async fn check_docker_desktop_update_available(
-    current_version: &str,
-    client: &reqwest::Client,
-) -> Option<bool> {
-    // Try Docker Desktop's official update endpoint
-    let response = client
-        .get("https://desktop.docker.com/api/updates/win/stable")
-        .header("Accept", "application/json")
-        .send()
-        .await
-        .ok()?;
-
-    if response.status().is_success() {
-        if let Ok(json) = response.json::<serde_json::Value>().await {
-            if let Some(latest_version) = json["version"].as_str() {
-                return Some(compare_versions(current_version, latest_version));
-            }
-        }
-    } else if response.status().as_u16() == 403 {
-        // API is restricted, try alternative approach
-        return check_docker_desktop_alternative_sources(current_version, client).await;
-    } else if response.status().as_u16() == 404 {
-        // Endpoint not found, try alternative approach
-        return check_docker_desktop_alternative_sources(current_version, client).await;
-    } else {
-        // Try alternative sources even on other errors
-        return check_docker_desktop_alternative_sources(current_version, client).await;
-    }
-
-    None
+fn is_active(state: &Arc<Mutex<DockerState>>) -> bool {
+    let name = {
+        let state_guard = state.lock().unwrap();
+        state_guard.endpoint.name.clone()
+    };
+    docker_endpoints::is_active_endpoint(&name)
 }
 
-// Alternative sources for Docker Desktop version checking
-async fn check_docker_desktop_alternative_sources(
-    current_version: &str,
-    client: &reqwest::Client,
-) -> Option<bool> {
-    // Try checking the installer file metadata
-    let installer_check = check_docker_desktop_installer_version(current_version, client).await;
-    if installer_check.is_some() {
-        return installer_check;
-    }
-
-    // Try checking Docker Desktop's download page for version info
-    let response = client
-        .get("https://desktop.docker.com/win/stable/")
-        .send()
-        .await;
-
-    match response {
-        Ok(response) => {
-            if response.status().is_success() {
-                if let Ok(html) = response.text().await {
-                    // Look for version information in the HTML
-                    // This is a simplified approach - in production you'd want more sophisticated parsing
-                    if html.contains("version") || html.contains("download") {
-                        // For now, return None to avoid false positives
-                        // In a real implementation, you'd parse the HTML for version info
-                        return None;
-                    }
-                }
-            }
-        }
-        Err(_) => {}
+// Apply an updated agent configuration to every endpoint. Running monitoring
+// loops rebuild their intervals when notified through the config subscription.
+pub(crate) fn apply_config(agent_config: &AgentConfig) {
+    let monitoring_config = MonitoringConfig::from(agent_config);
+    let http_client = build_http_client(&agent_config.http);
+    for state in docker_endpoints::all_states() {
+        let mut state_guard = state.lock().unwrap();
+        state_guard.config = monitoring_config.clone();
+        state_guard.http_client = http_client.clone();
     }
+}
 
-    // Manual check for known updates (temporary solution while API is restricted)
-    // Check if current version is 4.42.0.x and latest is 4.42.1
-    if current_version.starts_with("4.42.0") {
-        return Some(true);
-    }
+// Get a handle to the connected Docker client for commands in sibling modules
+pub(crate) fn get_docker_client() -> Result<Docker, DockerError> {
+    let state = get_docker_state();
+    let state_guard = state.lock().unwrap();
+    state_guard.docker.clone().ok_or(DockerError::NotRunning)
+}
 
-    None
+// Get the active endpoint's client and definition from one snapshot, so both
+// describe the same endpoint even if the active one changes meanwhile
+pub(crate) fn get_active_client() -> Result<(Docker, DockerEndpoint), DockerError> {
+    let state = get_docker_state();
+    let state_guard = state.lock().unwrap();
+    let docker = state_guard.docker.clone().ok_or(DockerError::NotRunning)?;
+    Ok((docker, state_guard.endpoint.clone()))
 }
 
-// Fallback method: check Docker Desktop installer metadata
-async fn check_docker_desktop_installer_version(
-    current_version: &str,
-    client: &reqwest::Client,
-) -> Option<bool> {
-    // Check the installer file for version information
-    let response = client
-        .head("https://desktop.docker.com/win/stable/Docker%20Desktop%20Installer.exe")
-        .send()
-        .await;
+// Get the configured connection timeout for one-off Docker API calls
+pub(crate) fn get_connection_timeout() -> Duration {
+    let state = get_docker_state();
+    let state_guard = state.lock().unwrap();
+    state_guard.config.connection_timeout
+}
 
-    match response {
-        Ok(response) => {
-            if response.status().is_success() {
-                // Some servers include version in headers
-                if let Some(version_header) = response.headers().get("x-version") {
-                    if let Ok(version_str) = version_header.to_str() {
-                        return Some(compare_versions(current_version, version_str));
-                    }
-                }
+// Get a copy of the current monitoring configuration
+pub(crate) fn get_monitoring_config() -> MonitoringConfig {
+    let state = get_docker_state();
+    let state_guard = state.lock().unwrap();
+    state_guard.config.clone()
+}
 
-                // Check Content-Length or other metadata that might indicate a new version
-                // This is a simplified approach - in production you'd want more sophisticated version detection
-                if let Some(content_length) = response.headers().get("content-length") {
-                    if let Ok(length_str) = content_length.to_str() {
-                        if let Ok(_length) = length_str.parse::<u64>() {
-                            // If file size changed significantly, it might be a new version
-                            // This is a heuristic and not 100% reliable
-                            return None; // For now, return None to avoid false positives
-                        }
-                    }
-                }
-            }
-        }
-        Err(_) => {}
-    }
+// Get how often per-container stats are emitted to the frontend
+pub(crate) fn get_stats_interval() -> Duration {
+    let state = get_docker_state();
+    let state_guard = state.lock().unwrap();
+    state_guard.config.stats_interval
+}
 
-    None
+// Get the shared HTTP client, which follows the configured timeout and user agent
+pub(crate) fn get_http_client() -> reqwest::Client {
+    let state = get_docker_state();
+    let state_guard = state.lock().unwrap();
+    state_guard.http_client.clone()
 }
 
-// Enhanced version comparison with better semantic versioning support
-fn compare_versions(current: &str, latest: &str) -> bool {
-    // Clean version strings
-    let current_clean = current.trim().trim_start_matches('v');
-    let latest_clean = latest.trim().trim_start_matches('v');
+// Set from the tray; paused endpoints skip reconnects, health checks and update checks
+static MONITORING_PAUSED: AtomicBool = AtomicBool::new(false);
 
-    // Normalize versions by removing trailing .0 parts
-    let normalize_version = |version: &str| -> String {
-        let parts: Vec<&str> = version.split('.').collect();
-        let mut normalized = Vec::new();
+pub(crate) fn is_monitoring_paused() -> bool {
+    MONITORING_PAUSED.load(Ordering::Relaxed)
+}
 
-        for (i, part) in parts.iter().enumerate() {
-            if i >= 3 && *part == "0" {
-                // Skip trailing .0 parts after the third component
-                continue;
-            }
-            normalized.push(*part);
-        }
+pub(crate) fn set_monitoring_paused(paused: bool) {
+    MONITORING_PAUSED.store(paused, Ordering::Relaxed);
+    tracing::info!(paused, "Monitoring pause changed");
+}
 
-        normalized.join(".")
+// Check the active endpoint now instead of waiting for the next health check
+pub(crate) async fn refresh_active_endpoint(app_handle: &tauri::AppHandle) {
+    let state = get_docker_state();
+    let connected = {
+        let state_guard = state.lock().unwrap();
+        state_guard.docker.is_some()
     };
-
-    let current_normalized = normalize_version(current_clean);
-    let latest_normalized = normalize_version(latest_clean);
-
-    // Parse version parts
-    let current_parts: Vec<u32> = current_normalized
-        .split('.')
-        .filter_map(|s| s.parse::<u32>().ok())
-        .collect();
-
-    let latest_parts: Vec<u32> = latest_normalized
-        .split('.')
-        .filter_map(|s| s.parse::<u32>().ok())
-        .collect();
-
-    // Ensure we have valid version numbers
-    if current_parts.is_empty() || latest_parts.is_empty() {
-        return false;
+    if !connected {
+        // Reconnecting is left to the retry loop, which also follows events
+        notify_status_update(app_handle, &state).await;
+        return;
     }
-
-    // Compare version parts
-    let max_len = std::cmp::max(current_parts.len(), latest_parts.len());
-
-    for i in 0..max_len {
-        let current_part = current_parts.get(i).unwrap_or(&0);
-        let latest_part = latest_parts.get(i).unwrap_or(&0);
-
-        if latest_part > current_part {
-            return true;
-        } else if latest_part < current_part {
-            return false;
-        }
+    if let Err(error) = perform_health_check(&state, app_handle).await {
+        tracing::debug!(%error, "Health check failed");
     }
+}
 
-    // Versions are equal
-    false
+// Check the configured engine update source for a newer Docker Engine
+async fn check_docker_engine_update_available(
+    current_version: &str,
+    client: &reqwest::Client,
+    config: &MonitoringConfig,
+) -> Option<UpdateInfo> {
+    docker_updates::check_for_update(
+        &config.engine_update_source,
+        config.update_channel,
+        current_version,
+        client,
+    )
+    .await
+}
+
+// Check the configured Docker Desktop update source, by default the feed for
+// the platform we run on
+async fn check_docker_desktop_update_available(
+    current_version: &str,
+    client: &reqwest::Client,
+    config: &MonitoringConfig,
+) -> Option<UpdateInfo> {
+    docker_updates::check_for_update(
+        &config.desktop_update_source,
+        config.update_channel,
+        current_version,
+        client,
+    )
+    .await
 }
 
 // Check for available updates with proper rate limiting and error handling
@@ -350,38 +412,32 @@ async fn check_for_updates(state: &Arc<Mutex<DockerState>>, app_handle: &tauri::
         state_guard.status.desktop_version.clone()
     };
 
-    // Extract http_client to avoid holding mutex across await
-    let http_client = {
+    // Extract http_client and config to avoid holding mutex across await
+    let (http_client, config) = {
         let state_guard = state.lock().unwrap();
-        state_guard.http_client.clone()
+        (state_guard.http_client.clone(), state_guard.config.clone())
     };
 
     // Check engine updates with timeout
-    let engine_update_available = if let Some(version) = &current_engine_version {
-        match tokio::time::timeout(
+    let engine_update = if let Some(version) = &current_engine_version {
+        tokio::time::timeout(
             Duration::from_secs(15),
-            check_docker_engine_update_available(version, &http_client),
+            check_docker_engine_update_available(version, &http_client, &config),
         )
         .await
-        {
-            Ok(result) => result,
-            Err(_) => None,
-        }
+        .unwrap_or_default()
     } else {
         None
     };
 
     // Check desktop updates with timeout
-    let desktop_update_available = if let Some(version) = &current_desktop_version {
-        match tokio::time::timeout(
+    let desktop_update = if let Some(version) = &current_desktop_version {
+        tokio::time::timeout(
             Duration::from_secs(15),
-            check_docker_desktop_update_available(version, &http_client),
+            check_docker_desktop_update_available(version, &http_client, &config),
         )
         .await
-        {
-            Ok(result) => result,
-            Err(_) => None,
-        }
+        .unwrap_or_default()
     } else {
         None
     };
@@ -389,8 +445,12 @@ async fn check_for_updates(state: &Arc<Mutex<DockerState>>, app_handle: &tauri::
     // Update state with update information and timestamp
     {
         let mut state_guard = state.lock().unwrap();
-        state_guard.status.engine_update_available = engine_update_available;
-        state_guard.status.desktop_update_available = desktop_update_available;
+        state_guard.status.engine_update_available =
+            engine_update.as_ref().map(|u| u.update_available);
+        state_guard.status.desktop_update_available =
+            desktop_update.as_ref().map(|u| u.update_available);
+        state_guard.status.engine_update = engine_update;
+        state_guard.status.desktop_update = desktop_update;
         state_guard.status.last_checked = Some(chrono::Utc::now().to_rfc3339());
         state_guard.last_update_check = Some(std::time::Instant::now());
     }
@@ -398,40 +458,56 @@ async fn check_for_updates(state: &Arc<Mutex<DockerState>>, app_handle: &tauri::
     notify_status_update(app_handle, state).await;
 }
 
-// Initialize Docker monitoring with proper error handling
+// Initialize Docker monitoring for every known endpoint
 pub async fn initialize_docker_monitoring(app_handle: tauri::AppHandle) {
-    let state = get_docker_state();
-    // Optionally, you could load config from a file or env here
-    // For now, use default config
-    {
-        let mut state_guard = state.lock().unwrap();
-        state_guard.config = MonitoringConfig::default();
-    } // Mutex guard is dropped here
+    docker_endpoints::start_all_endpoint_monitoring(&app_handle);
+}
+
+// Start the monitoring loop and initial connection for one endpoint, returning
+// the tasks so they can be stopped when the endpoint is removed
+pub(crate) fn spawn_endpoint_monitoring(
+    state: Arc<Mutex<DockerState>>,
+    app_handle: &tauri::AppHandle,
+) -> Vec<JoinHandle<()>> {
+    let span = {
+        let state_guard = state.lock().unwrap();
+        tracing::info_span!("endpoint", name = %state_guard.endpoint.name)
+    };
 
     // Start the monitoring loop first
+    let state_clone = Arc::clone(&state);
     let app_handle_clone = app_handle.clone();
-    tauri::async_runtime::spawn(async move {
-        docker_monitoring_loop(state, app_handle_clone).await;
-    });
+    let monitoring = tauri::async_runtime::spawn(
+        async move {
+            docker_monitoring_loop(state_clone, app_handle_clone).await;
+        }
+        .instrument(span.clone()),
+    );
 
     // Then attempt initial connection
-    let state_clone = Arc::clone(&get_docker_state());
     let app_handle_clone = app_handle.clone();
-    tauri::async_runtime::spawn(async move {
-        if let Ok(()) = try_connect_docker(&state_clone, &app_handle_clone).await {
-            // If initial connection succeeds, start event monitoring
-            let _ = start_event_monitoring(&state_clone, &app_handle_clone).await;
+    let initial_connection = tauri::async_runtime::spawn(
+        async move {
+            match try_connect_docker(&state, &app_handle_clone).await {
+                // If initial connection succeeds, start event monitoring
+                Ok(()) => spawn_event_monitoring(&state, &app_handle_clone),
+                Err(error) => tracing::debug!(%error, "Initial connection failed"),
+            }
         }
-    });
+        .instrument(span),
+    );
+
+    vec![monitoring, initial_connection]
 }
 
 // Main Docker monitoring loop using config values
 async fn docker_monitoring_loop(state: Arc<Mutex<DockerState>>, app_handle: tauri::AppHandle) {
     // Get config values (clone to avoid holding lock)
-    let config = {
+    let mut config = {
         let state_guard = state.lock().unwrap();
         state_guard.config.clone()
     };
+    let mut config_updates = config::subscribe_to_config();
     let mut retry_interval = tokio::time::interval(config.retry_interval);
     let mut health_check_interval = tokio::time::interval(config.health_check_interval);
     let mut update_check_interval = tokio::time::interval(config.update_check_interval);
@@ -442,80 +518,25 @@ async fn docker_monitoring_loop(state: Arc<Mutex<DockerState>>, app_handle: taur
     loop {
         tokio::select! {
             _ = retry_interval.tick() => {
-                let state_clone = Arc::clone(&state);
-                let app_handle_clone = app_handle.clone();
-                let try_connect = async move {
-                    try_connect_docker(&state_clone, &app_handle_clone).await
-                };
-                match try_connect.await {
-                    Ok(()) => {
-                        // Reset retry count on successful connection
-                        {
-                            let mut state_guard = state.lock().unwrap();
-                            state_guard.retry_count = 0;
-                        }
-                        let state_clone = Arc::clone(&state);
-                        let app_handle_clone = app_handle.clone();
-                        let start_events = async move {
-                            start_event_monitoring(&state_clone, &app_handle_clone).await
-                        };
-                        if let Err(_) = start_events.await {
-                            // Event monitoring failed, we'll retry on next health check
-                        }
-                    }
-                    Err(error) => {
-                        // Handle different error types appropriately
-                        let error_message = match error {
-                            DockerError::NotRunning => "Docker is not running",
-                            DockerError::StartingUp => "Docker is starting up",
-                            DockerError::Timeout => "Docker connection timeout",
-                            DockerError::ConnectionLost => "Docker connection lost",
-                            DockerError::Restarting => "Docker is restarting",
-                            DockerError::ConnectionFailed(_msg) => {
-                                "Docker connection failed"
-                            }
-                        };
-
-                        // Update status with appropriate error message
-                        {
-                            let mut state_guard = state.lock().unwrap();
-                            state_guard.status.error = Some(error_message.to_string());
-                            state_guard.status.is_running = false;
-                            state_guard.status.engine_version = None;
-                            state_guard.status.desktop_version = None;
-                            state_guard.status.engine_update_available = None;
-                            state_guard.status.desktop_update_available = None;
-                            state_guard.status.container_count = None;
-                            state_guard.status.last_checked = Some(chrono::Utc::now().to_rfc3339());
-                        }
-                        notify_status_update(&app_handle, &state).await;
-
-                        // Connection failed, implement exponential backoff up to max_retries
-                        let (retry_count, max_retries) = {
-                            let mut state_guard = state.lock().unwrap();
-                            state_guard.retry_count = state_guard.retry_count.saturating_add(1);
-                            (state_guard.retry_count, state_guard.config.max_retries)
-                        };
-                        if retry_count >= max_retries {
-                            // Use faster retry interval for restart scenarios
-                            tokio::time::sleep(Duration::from_millis(500)).await;
-                            continue;
-                        }
-                        // Use faster backoff for restart detection
-                        let backoff_duration = Duration::from_millis(500) * 2u32.pow(retry_count.min(3));
-                        tokio::time::sleep(backoff_duration).await;
-                        continue;
-                    }
+                if is_monitoring_paused() {
+                    continue;
                 }
+                retry_connection(&state, &app_handle)
+                    .instrument(tracing::debug_span!("monitor", tick = "retry"))
+                    .await;
             }
             _ = health_check_interval.tick() => {
-                let state_clone = Arc::clone(&state);
-                let app_handle_clone = app_handle.clone();
-                let health_check = async move {
-                    perform_health_check(&state_clone, &app_handle_clone).await
+                if is_monitoring_paused() {
+                    continue;
+                }
+                let health_check = async {
+                    if let Err(error) = perform_health_check(&state, &app_handle).await {
+                        tracing::debug!(%error, "Health check failed");
+                    }
                 };
-                // Simple error handling without complex state updates
-                let _ = health_check.await;
+                health_check
+                    .instrument(tracing::debug_span!("monitor", tick = "health_check"))
+                    .await;
             }
             _ = update_check_interval.tick() => {
                 let should_check = {
@@ -526,33 +547,120 @@ async fn docker_monitoring_loop(state: Arc<Mutex<DockerState>>, app_handle: taur
                         true
                     }
                 }; // Mutex guard is dropped here
-                if should_check {
-                    check_for_updates(&state, &app_handle).await;
+                if should_check && !is_monitoring_paused() {
+                    check_for_updates(&state, &app_handle)
+                        .instrument(tracing::debug_span!("monitor", tick = "update_check"))
+                        .await;
+                }
+            }
+            Ok(()) = config_updates.changed() => {
+                // Rebuild only the intervals that changed so their schedule is kept otherwise
+                let new_config = {
+                    let state_guard = state.lock().unwrap();
+                    state_guard.config.clone()
+                };
+                if new_config.retry_interval != config.retry_interval {
+                    retry_interval = tokio::time::interval(new_config.retry_interval);
+                }
+                if new_config.health_check_interval != config.health_check_interval {
+                    health_check_interval = tokio::time::interval(new_config.health_check_interval);
+                }
+                if new_config.update_check_interval != config.update_check_interval {
+                    update_check_interval = tokio::time::interval(new_config.update_check_interval);
                 }
+                tracing::debug!(config = ?new_config, "Monitoring config reloaded");
+                config = new_config;
             }
         }
     }
 }
 
+// One retry tick of the monitoring loop: connect and start following events,
+// or back off after a failed attempt
+async fn retry_connection(state: &Arc<Mutex<DockerState>>, app_handle: &tauri::AppHandle) {
+    // A live event stream means the connection is up
+    if event_stream_active(state) {
+        return;
+    }
+    match try_connect_docker(state, app_handle).await {
+        Ok(()) => {
+            // Reset retry count on successful connection
+            {
+                let mut state_guard = state.lock().unwrap();
+                state_guard.retry_count = 0;
+            }
+            spawn_event_monitoring(state, app_handle);
+        }
+        Err(error) => {
+            tracing::debug!(%error, "Connection attempt failed");
+            // Handle different error types appropriately
+            let error_message = match error {
+                DockerError::NotRunning => "Docker is not running",
+                DockerError::StartingUp => "Docker is starting up",
+                DockerError::Timeout => "Docker connection timeout",
+                DockerError::ConnectionLost => "Docker connection lost",
+                DockerError::Restarting => "Docker is restarting",
+                DockerError::ConnectionFailed(_msg) => "Docker connection failed",
+                DockerError::PermissionDenied(_msg) => {
+                    "Permission denied while connecting to Docker"
+                }
+                DockerError::NotFound(_)
+                | DockerError::Conflict(_)
+                | DockerError::RequestFailed(_)
+                | DockerError::InvalidInput(_) => "Docker request failed",
+            };
+
+            // Update status with appropriate error message
+            {
+                let mut state_guard = state.lock().unwrap();
+                state_guard.status.error = Some(error_message.to_string());
+                state_guard.status.is_running = false;
+                state_guard.status.engine_version = None;
+                state_guard.status.host = None;
+                state_guard.status.engine_flavor = None;
+                state_guard.status.desktop_version = None;
+                state_guard.status.engine_update_available = None;
+                state_guard.status.desktop_update_available = None;
+                state_guard.status.engine_update = None;
+                state_guard.status.desktop_update = None;
+                state_guard.status.container_count = None;
+                state_guard.status.last_checked = Some(chrono::Utc::now().to_rfc3339());
+            }
+            notify_status_update(app_handle, state).await;
+
+            // Connection failed, implement exponential backoff up to max_retries
+            let (retry_count, max_retries) = {
+                let mut state_guard = state.lock().unwrap();
+                state_guard.retry_count = state_guard.retry_count.saturating_add(1);
+                (state_guard.retry_count, state_guard.config.max_retries)
+            };
+            let backoff_duration = if retry_count >= max_retries {
+                // Use faster retry interval for restart scenarios
+                Duration::from_millis(500)
+            } else {
+                // Use faster backoff for restart detection
+                Duration::from_millis(500) * 2u32.pow(retry_count.min(3))
+            };
+            tokio::time::sleep(backoff_duration).await;
+        }
+    }
+}
+
 // Try to connect to Docker with proper timeout and error handling
 async fn try_connect_docker(
     state: &Arc<Mutex<DockerState>>,
     app_handle: &tauri::AppHandle,
 ) -> Result<(), DockerError> {
-    let config = {
+    let (config, endpoint) = {
         let state_guard = state.lock().unwrap();
-        state_guard.config.clone()
+        (state_guard.config.clone(), state_guard.endpoint.clone())
     };
 
-    // Connect to Docker with proper error handling
-    let docker = Docker::connect_with_local_defaults()
-        .map_err(|e| DockerError::ConnectionFailed(e.to_string()))?;
-
-    // Test connection by getting version with timeout
-    let version_info = tokio::time::timeout(config.connection_timeout, docker.version())
-        .await
-        .map_err(|_| DockerError::Timeout)?
-        .map_err(|_| DockerError::NotRunning)?;
+    // Connect and test the connection by getting the version with timeout. The
+    // default endpoint probes the known local sockets until one answers.
+    let (docker, version_info, host) =
+        docker_endpoints::connect_and_probe(&endpoint, config.connection_timeout).await?;
+    let engine_flavor = EngineFlavor::from_version(&version_info);
 
     let engine_version = DockerVersion {
         version: version_info
@@ -569,7 +677,18 @@ async fn try_connect_docker(
             .unwrap_or_else(|| "Architecture information unavailable".to_string()),
     };
 
-    let desktop_version = get_docker_desktop_version();
+    // Docker Desktop details only describe daemons on this machine
+    let desktop_version = if endpoint.is_local() {
+        // Detection runs external programs and reads files
+        tauri::async_runtime::spawn_blocking(get_docker_desktop_version)
+            .await
+            .unwrap_or_else(|error| {
+                tracing::warn!(%error, "Docker Desktop version detection panicked");
+                None
+            })
+    } else {
+        None
+    };
 
     // Get container count efficiently
     let container_count = tokio::time::timeout(
@@ -593,45 +712,56 @@ async fn try_connect_docker(
     // Check for updates asynchronously without blocking
     let engine_version_clone = engine_version.clone();
     let desktop_version_clone = desktop_version.clone();
+    let config_clone = config.clone();
     let state_clone = Arc::clone(state);
     let app_handle_clone = app_handle.clone();
 
     tauri::async_runtime::spawn(async move {
-        let engine_update_available =
-            check_docker_engine_update_available(&engine_version_clone.version, &http_client).await;
-        let desktop_update_available = if let Some(version) = &desktop_version_clone {
-            check_docker_desktop_update_available(version, &http_client).await
+        let engine_update = check_docker_engine_update_available(
+            &engine_version_clone.version,
+            &http_client,
+            &config_clone,
+        )
+        .await;
+        let desktop_update = if let Some(version) = &desktop_version_clone {
+            check_docker_desktop_update_available(version, &http_client, &config_clone).await
         } else {
             None
         };
 
         // Update status with update information - avoid holding mutex across await
-        let engine_update = engine_update_available;
-        let desktop_update = desktop_update_available;
-
         {
             let mut state_guard = state_clone.lock().unwrap();
             if let Some(engine_update) = engine_update {
-                state_guard.status.engine_update_available = Some(engine_update);
+                state_guard.status.engine_update_available = Some(engine_update.update_available);
+                state_guard.status.engine_update = Some(engine_update);
             }
             if let Some(desktop_update) = desktop_update {
-                state_guard.status.desktop_update_available = Some(desktop_update);
+                state_guard.status.desktop_update_available = Some(desktop_update.update_available);
+                state_guard.status.desktop_update = Some(desktop_update);
             }
         } // Mutex guard is dropped here
 
         notify_status_update(&app_handle_clone, &state_clone).await;
     });
 
+    tracing::info!(%host, flavor = ?engine_flavor, "Connected to Docker");
+
     // Update state with connection information
     {
         let mut state_guard = state.lock().unwrap();
         state_guard.docker = Some(docker);
         state_guard.status = DockerStatus {
+            endpoint: endpoint.name,
             is_running: true,
+            host: Some(host),
+            engine_flavor: Some(engine_flavor),
             engine_version: Some(engine_version),
             desktop_version,
             engine_update_available: None, // Will be updated by async task
             desktop_update_available: None, // Will be updated by async task
+            engine_update: None,
+            desktop_update: None,
             error: None,
             container_count,
             last_checked: Some(chrono::Utc::now().to_rfc3339()),
@@ -642,6 +772,51 @@ async fn try_connect_docker(
     Ok(())
 }
 
+fn event_stream_active(state: &Arc<Mutex<DockerState>>) -> bool {
+    let state_guard = state.lock().unwrap();
+    state_guard
+        .event_task
+        .as_ref()
+        .is_some_and(|task| !task.inner().is_finished())
+}
+
+// Follow the endpoint's events in their own task so the monitoring loop keeps
+// running. Does nothing while a previous stream is still being followed.
+fn spawn_event_monitoring(state: &Arc<Mutex<DockerState>>, app_handle: &tauri::AppHandle) {
+    let mut state_guard = state.lock().unwrap();
+    if state_guard
+        .event_task
+        .as_ref()
+        .is_some_and(|task| !task.inner().is_finished())
+    {
+        tracing::debug!("Event stream already followed by another task");
+        return;
+    }
+
+    let state_clone = Arc::clone(state);
+    let app_handle_clone = app_handle.clone();
+    state_guard.event_task = Some(tauri::async_runtime::spawn(
+        async move {
+            if let Err(error) = start_event_monitoring(&state_clone, &app_handle_clone).await {
+                // The monitoring loop reconnects on its next retry tick
+                tracing::info!(%error, "Event monitoring stopped");
+            }
+        }
+        .in_current_span(),
+    ));
+}
+
+// Stop following an endpoint's events, e.g. when the endpoint is removed
+pub(crate) fn stop_event_monitoring(state: &Arc<Mutex<DockerState>>) {
+    let task = {
+        let mut state_guard = state.lock().unwrap();
+        state_guard.event_task.take()
+    };
+    if let Some(task) = task {
+        task.abort();
+    }
+}
+
 // Start monitoring Docker events with proper error handling
 async fn start_event_monitoring(
     state: &Arc<Mutex<DockerState>>,
@@ -657,17 +832,37 @@ async fn start_event_monitoring(
 
     let mut events = docker.events(None::<EventsOptions>);
 
+    // Watch stats for containers that were already running before we connected.
+    // Container-level tracking only follows the active endpoint.
+    if is_active(state) {
+        if let Err(error) = docker_stats::attach_running_containers(app_handle).await {
+            tracing::warn!(%error, "Could not attach stats to running containers");
+        }
+        if let Err(error) = docker_health::seed_container_health(app_handle).await {
+            tracing::warn!(%error, "Could not read container health");
+        }
+    }
+
     while let Some(event_result) = events.next().await {
         match event_result {
-            Ok(_event) => {
+            Ok(event) => {
+                let event = DockerEvent::from(event);
+                record_event(state, app_handle, &event);
+                if is_active(state) {
+                    docker_stats::handle_container_event(app_handle, &event);
+                    docker_exec::handle_container_event(app_handle, &event);
+                    docker_health::handle_container_event(app_handle, &event).await;
+                }
                 // Docker event received, perform health check
-                if let Err(_) = perform_health_check(state, app_handle).await {
+                if let Err(error) = perform_health_check(state, app_handle).await {
+                    tracing::debug!(%error, "Health check failed after event");
                     break; // Connection lost, exit event loop
                 }
             }
-            Err(_e) => {
+            Err(error) => {
                 // Event stream broke. Connection is lost. We don't know why.
                 // The most accurate state is "reconnecting".
+                tracing::warn!(%error, "Docker event stream ended");
                 {
                     let mut state_guard = state.lock().unwrap();
                     // Only change state if we were previously running to avoid incorrect state changes.
@@ -686,6 +881,10 @@ async fn start_event_monitoring(
         }
     }
 
+    if is_active(state) {
+        docker_stats::detach_all_container_stats();
+        docker_exec::close_all_exec_sessions(app_handle);
+    }
     Err(DockerError::ConnectionLost)
 }
 
@@ -734,7 +933,8 @@ async fn perform_health_check(
             notify_status_update(app_handle, state).await;
             Ok(())
         }
-        Ok(Err(_)) => {
+        Ok(Err(error)) => {
+            tracing::warn!(%error, "Docker did not answer ping");
             // Docker is not responding - only update if we were previously running
             {
                 let mut state_guard = state.lock().unwrap();
@@ -749,6 +949,7 @@ async fn perform_health_check(
             Err(DockerError::ConnectionLost)
         }
         Err(_) => {
+            tracing::warn!(timeout = ?config.connection_timeout, "Docker ping timed out");
             // Timeout occurred - only update if we were previously running
             {
                 let mut state_guard = state.lock().unwrap();
@@ -765,14 +966,44 @@ async fn perform_health_check(
     }
 }
 
-// Notify frontend of status update with proper error handling
+// Store an event in the bounded history and forward it to the frontend
+fn record_event(
+    state: &Arc<Mutex<DockerState>>,
+    app_handle: &tauri::AppHandle,
+    event: &DockerEvent,
+) {
+    {
+        let mut state_guard = state.lock().unwrap();
+        if state_guard.recent_events.len() >= MAX_RECENT_EVENTS {
+            state_guard.recent_events.pop_front();
+        }
+        state_guard.recent_events.push_back(event.clone());
+    }
+
+    if is_active(state) {
+        if let Err(error) = app_handle.emit("docker-event", event) {
+            tracing::warn!(%error, "Could not emit docker-event");
+        }
+    }
+}
+
+// Notify frontend of status update with proper error handling. Every endpoint
+// reports on `docker-endpoint-status-updated`; the active one also on `docker-status-updated`.
 async fn notify_status_update(app_handle: &tauri::AppHandle, state: &Arc<Mutex<DockerState>>) {
     let status = {
         let state_guard = state.lock().unwrap();
         state_guard.status.clone()
     };
 
-    let _ = app_handle.emit("docker-status-updated", status);
+    notifications::observe_docker_status(&status);
+    if is_active(state) {
+        if let Err(error) = app_handle.emit("docker-status-updated", status.clone()) {
+            tracing::warn!(%error, "Could not emit docker-status-updated");
+        }
+    }
+    if let Err(error) = app_handle.emit("docker-endpoint-status-updated", status) {
+        tracing::warn!(%error, "Could not emit docker-endpoint-status-updated");
+    }
 }
 
 #[tauri::command]
@@ -794,6 +1025,33 @@ pub async fn get_docker_version() -> Result<DockerVersion, String> {
         .ok_or_else(|| "Docker version not available".to_string())
 }
 
+// Returns the most recent events first, optionally filtered by type and action
+#[tauri::command]
+pub async fn get_recent_docker_events(
+    event_type: Option<String>,
+    action: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<DockerEvent>, String> {
+    let state = get_docker_state();
+    let state_guard = state.lock().unwrap();
+
+    let events = state_guard
+        .recent_events
+        .iter()
+        .rev()
+        .filter(|e| event_type.as_ref().is_none_or(|t| &e.event_type == t))
+        .filter(|e| {
+            action
+                .as_ref()
+                .is_none_or(|a| &e.action == a || e.base_action() == a)
+        })
+        .take(limit.unwrap_or(MAX_RECENT_EVENTS))
+        .cloned()
+        .collect();
+
+    Ok(events)
+}
+
 #[tauri::command]
 pub async fn subscribe_to_docker_events(app_handle: tauri::AppHandle) -> Result<(), String> {
     let state = get_docker_state();
@@ -804,91 +1062,16 @@ pub async fn subscribe_to_docker_events(app_handle: tauri::AppHandle) -> Result<
 
     tauri::async_runtime::spawn(async move {
         while let Ok(status) = receiver.recv().await {
-            let _ = app_handle.emit("docker-status-updated", status);
+            if let Err(error) = app_handle.emit("docker-status-updated", status) {
+                tracing::warn!(%error, "Could not emit docker-status-updated");
+            }
         }
     });
 
     Ok(())
 }
 
-// Get Docker Desktop version from Windows registry with proper error handling
+// Get the installed Docker Desktop version on any supported platform
 fn get_docker_desktop_version() -> Option<String> {
-    // Try to get version from registry
-    let output = std::process::Command::new("reg")
-        .args(&[
-            "query",
-            r"HKEY_LOCAL_MACHINE\SOFTWARE\Docker Inc.\Docker Desktop",
-            "/v",
-            "CurrentVersion",
-        ])
-        .output();
-
-    match output {
-        Ok(output) if output.status.success() => {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            // Parse the registry output to extract version
-            if let Some(line) = output_str
-                .lines()
-                .find(|line| line.contains("CurrentVersion"))
-            {
-                if let Some(version) = line.split_whitespace().last() {
-                    // Clean up the version string (remove quotes, etc.)
-                    let clean_version = version.trim_matches('"').trim();
-                    if !clean_version.is_empty() {
-                        // Remove trailing .0 parts that Windows registry incorrectly adds
-                        let normalized_version = clean_version
-                            .split('.')
-                            .collect::<Vec<&str>>()
-                            .into_iter()
-                            .take(3) // Only take first 3 parts (major.minor.patch)
-                            .collect::<Vec<&str>>()
-                            .join(".");
-
-                        return Some(normalized_version);
-                    }
-                }
-            }
-        }
-        Err(_) => {}
-        _ => {}
-    }
-
-    // Fallback: try to get version from Docker Desktop executable
-    let output = std::process::Command::new("wmic")
-        .args(&[
-            "datafile",
-            "where",
-            r"name='C:\\Program Files\\Docker\\Docker\\Docker Desktop.exe'",
-            "get",
-            "version",
-            "/value",
-        ])
-        .output();
-
-    match output {
-        Ok(output) if output.status.success() => {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            if let Some(line) = output_str.lines().find(|line| line.contains("Version=")) {
-                if let Some(version) = line.split('=').nth(1) {
-                    let clean_version = version.trim();
-                    if !clean_version.is_empty() {
-                        // Remove trailing .0 parts that WMIC might add
-                        let normalized_version = clean_version
-                            .split('.')
-                            .collect::<Vec<&str>>()
-                            .into_iter()
-                            .take(3) // Only take first 3 parts (major.minor.patch)
-                            .collect::<Vec<&str>>()
-                            .join(".");
-
-                        return Some(normalized_version);
-                    }
-                }
-            }
-        }
-        Err(_) => {}
-        _ => {}
-    }
-
-    None
+    DesktopEnvironment::system().desktop_version()
 }
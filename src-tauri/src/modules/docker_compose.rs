@@ -0,0 +1,389 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::docker::DockerError;
+use super::docker_containers::{
+    list_containers, run_container_action, ContainerAction, ContainerActionResult,
+    ContainerFilters, ContainerSummary,
+};
+
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+const WORKING_DIR_LABEL: &str = "com.docker.compose.project.working_dir";
+const CONFIG_FILES_LABEL: &str = "com.docker.compose.project.config_files";
+/// Written by Compose 2.22 and later as `service:condition:restart` entries
+/// separated by commas
+const DEPENDS_ON_LABEL: &str = "com.docker.compose.depends_on";
+
+/// Overall state of a service or project, derived from its containers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComposeState {
+    Running,
+    Partial,
+    Stopped,
+}
+
+/// One service of a compose project and its replicas
+#[derive(Debug, Clone, Serialize)]
+pub struct ComposeService {
+    pub name: String,
+    pub replicas: usize,
+    pub running: usize,
+    pub healthy: usize,
+    pub unhealthy: usize,
+    pub state: ComposeState,
+    pub containers: Vec<ContainerSummary>,
+}
+
+/// Containers grouped by their compose project label
+#[derive(Debug, Clone, Serialize)]
+pub struct ComposeProject {
+    pub name: String,
+    pub working_dir: Option<String>,
+    pub config_files: Vec<String>,
+    pub state: ComposeState,
+    pub unhealthy_services: usize,
+    pub services: Vec<ComposeService>,
+}
+
+/// A container that failed during a project-wide action
+#[derive(Debug, Clone, Serialize)]
+pub struct ComposeActionFailure {
+    pub container: String,
+    pub error: DockerError,
+}
+
+/// Outcome of starting, stopping or restarting every container in a project
+#[derive(Debug, Clone, Serialize)]
+pub struct ComposeActionResult {
+    pub project: String,
+    pub succeeded: Vec<ContainerActionResult>,
+    pub failed: Vec<ComposeActionFailure>,
+}
+
+fn state_from_counts(running: usize, total: usize) -> ComposeState {
+    if total > 0 && running == total {
+        ComposeState::Running
+    } else if running > 0 {
+        ComposeState::Partial
+    } else {
+        ComposeState::Stopped
+    }
+}
+
+fn build_service(name: String, containers: Vec<ContainerSummary>) -> ComposeService {
+    let running = containers.iter().filter(|c| c.state == "running").count();
+    let health_count = |value: &str| {
+        containers
+            .iter()
+            .filter(|c| c.health.as_deref() == Some(value))
+            .count()
+    };
+
+    ComposeService {
+        replicas: containers.len(),
+        running,
+        healthy: health_count("healthy"),
+        unhealthy: health_count("unhealthy"),
+        state: state_from_counts(running, containers.len()),
+        name,
+        containers,
+    }
+}
+
+/// Groups compose-managed containers into projects and services, sorted by name
+pub(crate) fn group_projects(containers: Vec<ContainerSummary>) -> Vec<ComposeProject> {
+    let mut grouped: BTreeMap<String, BTreeMap<String, Vec<ContainerSummary>>> = BTreeMap::new();
+    let mut metadata: BTreeMap<String, (Option<String>, Vec<String>)> = BTreeMap::new();
+
+    for container in containers {
+        let project = match container.labels.get(PROJECT_LABEL) {
+            Some(project) => project.clone(),
+            None => continue,
+        };
+        let service = container
+            .labels
+            .get(SERVICE_LABEL)
+            .cloned()
+            .unwrap_or_default();
+
+        metadata.entry(project.clone()).or_insert_with(|| {
+            let config_files = container
+                .labels
+                .get(CONFIG_FILES_LABEL)
+                .map(|files| files.split(',').map(str::to_string).collect())
+                .unwrap_or_default();
+            (
+                container.labels.get(WORKING_DIR_LABEL).cloned(),
+                config_files,
+            )
+        });
+
+        grouped
+            .entry(project)
+            .or_default()
+            .entry(service)
+            .or_default()
+            .push(container);
+    }
+
+    grouped
+        .into_iter()
+        .map(|(name, services)| {
+            let services: Vec<ComposeService> = services
+                .into_iter()
+                .map(|(service, containers)| build_service(service, containers))
+                .collect();
+            let running = services.iter().map(|s| s.running).sum();
+            let total = services.iter().map(|s| s.replicas).sum();
+            let (working_dir, config_files) = metadata.remove(&name).unwrap_or_default();
+
+            ComposeProject {
+                state: state_from_counts(running, total),
+                unhealthy_services: services.iter().filter(|s| s.unhealthy > 0).count(),
+                working_dir,
+                config_files,
+                services,
+                name,
+            }
+        })
+        .collect()
+}
+
+async fn list_project_containers(
+    project: Option<&str>,
+) -> Result<Vec<ContainerSummary>, DockerError> {
+    let label = match project {
+        Some(project) => format!("{}={}", PROJECT_LABEL, project),
+        None => PROJECT_LABEL.to_string(),
+    };
+
+    list_containers(&ContainerFilters {
+        label: vec![label],
+        ..Default::default()
+    })
+    .await
+}
+
+/// What to do to a container in `state` for a project-wide `action`, or
+/// `None` when it is already where the action would leave it
+fn container_action(action: ContainerAction, state: &str) -> Option<ContainerAction> {
+    match (action, state) {
+        (ContainerAction::Start, "running") => None,
+        // Docker refuses to start a paused container; resuming it is the equivalent
+        (ContainerAction::Start, "paused") => Some(ContainerAction::Unpause),
+        (ContainerAction::Stop, "running" | "paused") => Some(ContainerAction::Stop),
+        (ContainerAction::Stop, _) => None,
+        (action, _) => Some(action),
+    }
+}
+
+fn service_of(container: &ContainerSummary) -> &str {
+    container
+        .labels
+        .get(SERVICE_LABEL)
+        .map(String::as_str)
+        .unwrap_or_default()
+}
+
+/// Orders containers so every service comes after the services it depends on,
+/// keeping the listing order otherwise. Dependency cycles are broken in
+/// listing order.
+fn order_by_dependencies(containers: Vec<ContainerSummary>) -> Vec<ContainerSummary> {
+    let mut services: Vec<&str> = Vec::new();
+    let mut depends_on: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for container in &containers {
+        let service = service_of(container);
+        if !services.contains(&service) {
+            services.push(service);
+        }
+        let dependencies = container
+            .labels
+            .get(DEPENDS_ON_LABEL)
+            .into_iter()
+            .flat_map(|label| label.split(','))
+            .filter_map(|entry| entry.split(':').next())
+            .map(str::trim)
+            .filter(|dependency| !dependency.is_empty() && *dependency != service);
+        depends_on.entry(service).or_default().extend(dependencies);
+    }
+
+    let mut ordered: Vec<&str> = Vec::new();
+    while ordered.len() < services.len() {
+        let remaining = || services.iter().filter(|s| !ordered.contains(s));
+        let ready = remaining().find(|service| {
+            depends_on[*service]
+                .iter()
+                .all(|dependency| ordered.contains(dependency) || !services.contains(dependency))
+        });
+        let next = ready.or_else(|| remaining().next()).copied();
+        ordered.extend(next);
+    }
+
+    let rank: HashMap<String, usize> = ordered
+        .iter()
+        .enumerate()
+        .map(|(index, service)| (service.to_string(), index))
+        .collect();
+    let mut containers = containers;
+    // Stable, so replicas of a service keep their listing order
+    containers.sort_by_key(|container| rank[service_of(container)]);
+    containers
+}
+
+/// Applies an action to every container of a project, continuing past
+/// failures. Services are started and restarted after the services they
+/// depend on, and stopped before them.
+async fn run_project_action(
+    project: &str,
+    action: ContainerAction,
+    timeout_secs: Option<i32>,
+) -> Result<ComposeActionResult, DockerError> {
+    let mut containers = list_project_containers(Some(project)).await?;
+    if containers.is_empty() {
+        return Err(DockerError::NotFound(format!(
+            "compose project {}",
+            project
+        )));
+    }
+
+    containers = order_by_dependencies(containers);
+    if action == ContainerAction::Stop {
+        containers.reverse();
+    }
+
+    let mut result = ComposeActionResult {
+        project: project.to_string(),
+        succeeded: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for container in containers {
+        // Skip containers that are already in the requested state
+        let Some(action) = container_action(action, &container.state) else {
+            continue;
+        };
+        match run_container_action(&container.id, action, timeout_secs, None, false, false).await {
+            Ok(outcome) => result.succeeded.push(outcome),
+            Err(error) => result.failed.push(ComposeActionFailure {
+                container: container.names.first().cloned().unwrap_or(container.id),
+                error,
+            }),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Tauri command to list compose projects with per-service replica counts and health
+#[tauri::command]
+pub async fn list_compose_projects() -> Result<Vec<ComposeProject>, DockerError> {
+    Ok(group_projects(list_project_containers(None).await?))
+}
+
+#[tauri::command]
+pub async fn start_compose_project(project: String) -> Result<ComposeActionResult, DockerError> {
+    run_project_action(&project, ContainerAction::Start, None).await
+}
+
+#[tauri::command]
+pub async fn stop_compose_project(
+    project: String,
+    timeout_secs: Option<i32>,
+) -> Result<ComposeActionResult, DockerError> {
+    run_project_action(&project, ContainerAction::Stop, timeout_secs).await
+}
+
+#[tauri::command]
+pub async fn restart_compose_project(
+    project: String,
+    timeout_secs: Option<i32>,
+) -> Result<ComposeActionResult, DockerError> {
+    run_project_action(&project, ContainerAction::Restart, timeout_secs).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container(name: &str, service: &str, depends_on: Option<&str>) -> ContainerSummary {
+        let mut labels = HashMap::from([
+            (PROJECT_LABEL.to_string(), "shop".to_string()),
+            (SERVICE_LABEL.to_string(), service.to_string()),
+        ]);
+        if let Some(depends_on) = depends_on {
+            labels.insert(DEPENDS_ON_LABEL.to_string(), depends_on.to_string());
+        }
+        ContainerSummary {
+            id: name.to_string(),
+            names: vec![name.to_string()],
+            image: "image".to_string(),
+            image_id: None,
+            command: None,
+            state: "exited".to_string(),
+            status: String::new(),
+            health: None,
+            ports: Vec::new(),
+            labels,
+            created: None,
+            size_rw: None,
+            size_root_fs: None,
+        }
+    }
+
+    fn ids(containers: Vec<ContainerSummary>) -> Vec<String> {
+        containers.into_iter().map(|c| c.id).collect()
+    }
+
+    #[test]
+    fn orders_services_after_their_dependencies() {
+        let containers = vec![
+            container("web-1", "web", Some("api:service_started:false")),
+            container(
+                "api-1",
+                "api",
+                Some("db:service_healthy:false,cache:service_started:true"),
+            ),
+            container("web-2", "web", Some("api:service_started:false")),
+            container("db-1", "db", None),
+            container("cache-1", "cache", None),
+        ];
+        assert_eq!(
+            ids(order_by_dependencies(containers)),
+            ["db-1", "cache-1", "api-1", "web-1", "web-2"]
+        );
+    }
+
+    #[test]
+    fn keeps_listing_order_without_or_despite_dependencies() {
+        let plain = vec![container("b-1", "b", None), container("a-1", "a", None)];
+        assert_eq!(ids(order_by_dependencies(plain)), ["b-1", "a-1"]);
+
+        // Neither side of a cycle can wait for the other
+        let cycle = vec![
+            container("a-1", "a", Some("b:service_started:false")),
+            container("b-1", "b", Some("a:service_started:false")),
+        ];
+        assert_eq!(ids(order_by_dependencies(cycle)), ["a-1", "b-1"]);
+
+        // Dependencies on services without containers are ignored
+        let missing = vec![
+            container("a-1", "a", Some("b:service_started:false")),
+            container("c-1", "c", Some("gone:service_started:false")),
+            container("b-1", "b", None),
+        ];
+        assert_eq!(ids(order_by_dependencies(missing)), ["c-1", "b-1", "a-1"]);
+    }
+
+    #[test]
+    fn starting_resumes_paused_containers_and_skips_running_ones() {
+        use ContainerAction::*;
+        assert_eq!(container_action(Start, "exited"), Some(Start));
+        assert_eq!(container_action(Start, "paused"), Some(Unpause));
+        assert_eq!(container_action(Start, "running"), None);
+        assert_eq!(container_action(Stop, "paused"), Some(Stop));
+        assert_eq!(container_action(Stop, "exited"), None);
+        assert_eq!(container_action(Restart, "exited"), Some(Restart));
+    }
+}
@@ -0,0 +1,370 @@
+use bollard::query_parameters::{
+    KillContainerOptions, ListContainersOptions, RemoveContainerOptions, RestartContainerOptions,
+    StopContainerOptions,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::docker::{get_connection_timeout, get_docker_client, DockerError};
+
+/// A published or exposed container port
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerPort {
+    pub ip: Option<String>,
+    pub private_port: u16,
+    pub public_port: Option<u16>,
+    pub protocol: Option<String>,
+}
+
+/// Per-container detail returned by `list_docker_containers`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerSummary {
+    pub id: String,
+    pub names: Vec<String>,
+    pub image: String,
+    pub image_id: Option<String>,
+    pub command: Option<String>,
+    pub state: String,
+    pub status: String,
+    /// "healthy", "unhealthy" or "starting" for containers with a health check
+    pub health: Option<String>,
+    pub ports: Vec<ContainerPort>,
+    pub labels: HashMap<String, String>,
+    pub created: Option<String>,
+    pub size_rw: Option<i64>,
+    pub size_root_fs: Option<i64>,
+}
+
+/// Lifecycle operations that can be applied to a container
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerAction {
+    Start,
+    Stop,
+    Restart,
+    Pause,
+    Unpause,
+    Kill,
+    Remove,
+}
+
+/// Outcome of a lifecycle operation, with the container state observed afterwards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerActionResult {
+    pub container: String,
+    pub action: ContainerAction,
+    /// `None` after a successful remove
+    pub state: Option<String>,
+}
+
+/// Filters accepted by `list_docker_containers`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ContainerFilters {
+    /// Container states, e.g. "running", "exited", "paused"
+    #[serde(default)]
+    pub state: Vec<String>,
+    /// Labels as "key" or "key=value"
+    #[serde(default)]
+    pub label: Vec<String>,
+    #[serde(default)]
+    pub name: Vec<String>,
+    /// Image name, id or digest the container was created from
+    #[serde(default)]
+    pub ancestor: Vec<String>,
+    /// Include stopped containers (defaults to true)
+    pub all: Option<bool>,
+    /// Ask the daemon to compute container sizes (slow on large hosts)
+    #[serde(default)]
+    pub size: bool,
+}
+
+impl ContainerFilters {
+    /// Converts the filters into the `map[string][]string` form the Docker API expects
+    fn to_query(&self) -> HashMap<String, Vec<String>> {
+        let mut filters = HashMap::new();
+        let entries = [
+            ("status", &self.state),
+            ("label", &self.label),
+            ("name", &self.name),
+            ("ancestor", &self.ancestor),
+        ];
+        for (key, values) in entries {
+            if !values.is_empty() {
+                filters.insert(key.to_string(), values.clone());
+            }
+        }
+        filters
+    }
+}
+
+/// Extracts the health state Docker appends to the status, e.g. "Up 2 hours (healthy)"
+fn parse_health(status: &str) -> Option<String> {
+    let start = status.rfind('(')?;
+    let inner = status[start + 1..].strip_suffix(')')?;
+    let health = inner.strip_prefix("health: ").unwrap_or(inner);
+    matches!(health, "healthy" | "unhealthy" | "starting").then(|| health.to_string())
+}
+
+impl From<bollard::models::ContainerSummary> for ContainerSummary {
+    fn from(container: bollard::models::ContainerSummary) -> Self {
+        let ports = container
+            .ports
+            .unwrap_or_default()
+            .into_iter()
+            .map(|port| ContainerPort {
+                ip: port.ip,
+                private_port: port.private_port,
+                public_port: port.public_port,
+                protocol: port.typ.map(|t| t.to_string()),
+            })
+            .collect();
+
+        // Docker reports names with a leading slash ("/web-1")
+        let names = container
+            .names
+            .unwrap_or_default()
+            .into_iter()
+            .map(|name| name.trim_start_matches('/').to_string())
+            .collect();
+
+        Self {
+            id: container.id.unwrap_or_default(),
+            names,
+            image: container.image.unwrap_or_default(),
+            image_id: container.image_id,
+            command: container.command,
+            state: container.state.map(|s| s.to_string()).unwrap_or_default(),
+            health: container.status.as_deref().and_then(parse_health),
+            status: container.status.unwrap_or_default(),
+            ports,
+            labels: container.labels.unwrap_or_default(),
+            created: container
+                .created
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                .map(|dt| dt.to_rfc3339()),
+            size_rw: container.size_rw,
+            size_root_fs: container.size_root_fs,
+        }
+    }
+}
+
+/// Lists containers with their full detail, optionally filtered
+pub(crate) async fn list_containers(
+    filters: &ContainerFilters,
+) -> Result<Vec<ContainerSummary>, DockerError> {
+    let docker = get_docker_client()?;
+
+    let options = ListContainersOptions {
+        all: filters.all.unwrap_or(true),
+        size: filters.size,
+        filters: Some(filters.to_query()),
+        ..Default::default()
+    };
+
+    let containers = tokio::time::timeout(
+        get_connection_timeout(),
+        docker.list_containers(Some(options)),
+    )
+    .await
+    .map_err(|_| DockerError::Timeout)??;
+
+    Ok(containers.into_iter().map(ContainerSummary::from).collect())
+}
+
+/// Tauri command to list containers with per-container detail
+#[tauri::command]
+pub async fn list_docker_containers(
+    filters: Option<ContainerFilters>,
+) -> Result<Vec<ContainerSummary>, String> {
+    list_containers(&filters.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Failed to list containers: {}", e))
+}
+
+/// Applies a lifecycle action to a container by id or name and reports its resulting state
+pub(crate) async fn run_container_action(
+    container: &str,
+    action: ContainerAction,
+    timeout_secs: Option<i32>,
+    signal: Option<String>,
+    force: bool,
+    remove_volumes: bool,
+) -> Result<ContainerActionResult, DockerError> {
+    let docker = get_docker_client()?;
+
+    match action {
+        ContainerAction::Start => {
+            docker
+                .start_container(
+                    container,
+                    None::<bollard::query_parameters::StartContainerOptions>,
+                )
+                .await?
+        }
+        ContainerAction::Stop => {
+            docker
+                .stop_container(
+                    container,
+                    Some(StopContainerOptions {
+                        signal,
+                        t: timeout_secs,
+                    }),
+                )
+                .await?
+        }
+        ContainerAction::Restart => {
+            docker
+                .restart_container(
+                    container,
+                    Some(RestartContainerOptions {
+                        signal,
+                        t: timeout_secs,
+                    }),
+                )
+                .await?
+        }
+        ContainerAction::Pause => docker.pause_container(container).await?,
+        ContainerAction::Unpause => docker.unpause_container(container).await?,
+        ContainerAction::Kill => {
+            docker
+                .kill_container(
+                    container,
+                    Some(KillContainerOptions {
+                        signal: signal.unwrap_or_else(|| "SIGKILL".to_string()),
+                    }),
+                )
+                .await?
+        }
+        ContainerAction::Remove => {
+            docker
+                .remove_container(
+                    container,
+                    Some(RemoveContainerOptions {
+                        v: remove_volumes,
+                        force,
+                        link: false,
+                    }),
+                )
+                .await?
+        }
+    }
+
+    // A removed container can no longer be inspected
+    let state = if action == ContainerAction::Remove {
+        None
+    } else {
+        docker
+            .inspect_container(
+                container,
+                None::<bollard::query_parameters::InspectContainerOptions>,
+            )
+            .await
+            .ok()
+            .and_then(|details| details.state)
+            .and_then(|state| state.status)
+            .map(|status| status.to_string())
+    };
+
+    Ok(ContainerActionResult {
+        container: container.to_string(),
+        action,
+        state,
+    })
+}
+
+#[tauri::command]
+pub async fn start_docker_container(
+    container: String,
+) -> Result<ContainerActionResult, DockerError> {
+    run_container_action(&container, ContainerAction::Start, None, None, false, false).await
+}
+
+/// `timeout_secs` is how long Docker waits for a graceful stop before killing the container
+#[tauri::command]
+pub async fn stop_docker_container(
+    container: String,
+    timeout_secs: Option<i32>,
+) -> Result<ContainerActionResult, DockerError> {
+    run_container_action(
+        &container,
+        ContainerAction::Stop,
+        timeout_secs,
+        None,
+        false,
+        false,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn restart_docker_container(
+    container: String,
+    timeout_secs: Option<i32>,
+) -> Result<ContainerActionResult, DockerError> {
+    run_container_action(
+        &container,
+        ContainerAction::Restart,
+        timeout_secs,
+        None,
+        false,
+        false,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn pause_docker_container(
+    container: String,
+) -> Result<ContainerActionResult, DockerError> {
+    run_container_action(&container, ContainerAction::Pause, None, None, false, false).await
+}
+
+#[tauri::command]
+pub async fn unpause_docker_container(
+    container: String,
+) -> Result<ContainerActionResult, DockerError> {
+    run_container_action(
+        &container,
+        ContainerAction::Unpause,
+        None,
+        None,
+        false,
+        false,
+    )
+    .await
+}
+
+/// Sends `signal` to the container (defaults to SIGKILL)
+#[tauri::command]
+pub async fn kill_docker_container(
+    container: String,
+    signal: Option<String>,
+) -> Result<ContainerActionResult, DockerError> {
+    run_container_action(
+        &container,
+        ContainerAction::Kill,
+        None,
+        signal,
+        false,
+        false,
+    )
+    .await
+}
+
+/// `force` kills a running container first; `remove_volumes` also deletes its anonymous volumes
+#[tauri::command]
+pub async fn remove_docker_container(
+    container: String,
+    force: Option<bool>,
+    remove_volumes: Option<bool>,
+) -> Result<ContainerActionResult, DockerError> {
+    run_container_action(
+        &container,
+        ContainerAction::Remove,
+        None,
+        None,
+        force.unwrap_or(false),
+        remove_volumes.unwrap_or(false),
+    )
+    .await
+}
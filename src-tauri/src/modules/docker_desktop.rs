@@ -0,0 +1,401 @@
+use std::path::{Path, PathBuf};
+
+use super::platform::{CommandRunner, Platform, SystemCommandRunner};
+
+/// Docker's release server. Callers pass the base URL explicitly so a local
+/// stand-in can serve the feeds instead.
+pub(crate) const DESKTOP_RELEASES_URL: &str = "https://desktop.docker.com";
+
+const DESKTOP_PACKAGE: &str = "docker-desktop";
+
+// Path segment Docker uses for the platform on its release server
+fn feed_name(platform: Platform) -> &'static str {
+    match platform {
+        Platform::Windows => "win",
+        Platform::MacOs => "mac",
+        Platform::Linux => "linux",
+    }
+}
+
+/// Where Docker Desktop keeps `componentsVersion.json`
+fn default_install_dir(platform: Platform) -> PathBuf {
+    match platform {
+        Platform::Windows => PathBuf::from(r"C:\Program Files\Docker\Docker\resources"),
+        Platform::MacOs => PathBuf::from("/Applications/Docker.app/Contents/Resources"),
+        Platform::Linux => PathBuf::from("/opt/docker-desktop"),
+    }
+}
+
+fn feed_arch() -> &'static str {
+    if cfg!(target_arch = "aarch64") {
+        "arm64"
+    } else {
+        "amd64"
+    }
+}
+
+/// Where and how to look for an installed Docker Desktop
+pub(crate) struct DesktopEnvironment<'a> {
+    pub(crate) platform: Platform,
+    pub(crate) runner: &'a dyn CommandRunner,
+    pub(crate) install_dir: PathBuf,
+}
+
+impl DesktopEnvironment<'static> {
+    /// The environment of the machine the agent runs on
+    pub(crate) fn system() -> Self {
+        let platform = Platform::current();
+        Self {
+            platform,
+            runner: &SystemCommandRunner,
+            install_dir: default_install_dir(platform),
+        }
+    }
+}
+
+impl DesktopEnvironment<'_> {
+    /// Installed Docker Desktop version as major.minor.patch, if any
+    pub(crate) fn desktop_version(&self) -> Option<String> {
+        if let Some(version) = read_components_version(&self.install_dir) {
+            return Some(version);
+        }
+
+        match self.platform {
+            Platform::Windows => self
+                .runner
+                .run(
+                    "reg",
+                    &[
+                        "query",
+                        r"HKEY_LOCAL_MACHINE\SOFTWARE\Docker Inc.\Docker Desktop",
+                        "/v",
+                        "CurrentVersion",
+                    ],
+                )
+                .and_then(|output| parse_registry_version(&output))
+                .or_else(|| {
+                    self.runner
+                        .run(
+                            "wmic",
+                            &[
+                                "datafile",
+                                "where",
+                                r"name='C:\\Program Files\\Docker\\Docker\\Docker Desktop.exe'",
+                                "get",
+                                "version",
+                                "/value",
+                            ],
+                        )
+                        .and_then(|output| parse_wmic_version(&output))
+                }),
+            Platform::MacOs => self
+                .runner
+                .run(
+                    "defaults",
+                    &[
+                        "read",
+                        "/Applications/Docker.app/Contents/Info.plist",
+                        "CFBundleShortVersionString",
+                    ],
+                )
+                .and_then(|output| normalize_version(output.trim())),
+            // Docker Desktop for Linux ships as a .deb or .rpm package
+            Platform::Linux => self
+                .runner
+                .run("dpkg-query", &["-W", "-f=${Version}", DESKTOP_PACKAGE])
+                .or_else(|| {
+                    self.runner
+                        .run("rpm", &["-q", "--qf", "%{VERSION}", DESKTOP_PACKAGE])
+                })
+                .and_then(|output| parse_package_version(&output)),
+        }
+    }
+}
+
+/// Keeps only major.minor.patch; Windows reports a fourth build component
+fn normalize_version(version: &str) -> Option<String> {
+    let version = version.trim().trim_matches('"').trim();
+    if version.is_empty() {
+        return None;
+    }
+    Some(version.split('.').take(3).collect::<Vec<_>>().join("."))
+}
+
+/// Reads the app version from Docker Desktop's `componentsVersion.json`
+fn read_components_version(install_dir: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(install_dir.join("componentsVersion.json")).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&contents).ok()?;
+    ["appVersion", "Version", "version"]
+        .iter()
+        .find_map(|key| json[key].as_str())
+        .and_then(normalize_version)
+}
+
+/// Parses `reg query ... /v CurrentVersion` output
+fn parse_registry_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find(|line| line.contains("CurrentVersion"))
+        .and_then(|line| line.split_whitespace().last())
+        .and_then(normalize_version)
+}
+
+/// Parses `wmic datafile ... get version /value` output
+fn parse_wmic_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Version="))
+        .and_then(normalize_version)
+}
+
+/// Parses a dpkg or rpm version such as `1:4.30.0-149282`, dropping the epoch
+/// and package revision
+fn parse_package_version(output: &str) -> Option<String> {
+    let version = output.trim();
+    let version = version.split_once(':').map_or(version, |(_, rest)| rest);
+    let version = version
+        .split_once('-')
+        .map_or(version, |(upstream, _)| upstream);
+    normalize_version(version)
+}
+
+/// Every `sparkle:shortVersionString` in a Sparkle appcast, which Docker
+/// Desktop uses as its update feed on every platform
+fn parse_appcast_versions(xml: &str) -> Vec<String> {
+    const TAG: &str = "<sparkle:shortVersionString>";
+    const ATTRIBUTE: &str = "sparkle:shortVersionString=\"";
+
+    let mut versions = Vec::new();
+    for (marker, end) in [(TAG, '<'), (ATTRIBUTE, '"')] {
+        let mut rest = xml;
+        while let Some(start) = rest.find(marker) {
+            rest = &rest[start + marker.len()..];
+            if let Some(len) = rest.find(end) {
+                let version = rest[..len].trim();
+                if !version.is_empty() {
+                    versions.push(version.to_string());
+                }
+            }
+        }
+    }
+    versions
+}
+
+async fn fetch_appcast_versions(
+    client: &reqwest::Client,
+    base_url: &str,
+    platform: Platform,
+) -> Result<Vec<String>, String> {
+    let url = format!(
+        "{}/{}/main/{}/appcast.xml",
+        base_url.trim_end_matches('/'),
+        feed_name(platform),
+        feed_arch()
+    );
+    let response = client.get(url).send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("appcast returned {}", response.status()));
+    }
+
+    let versions = parse_appcast_versions(&response.text().await.map_err(|e| e.to_string())?);
+    if versions.is_empty() {
+        return Err("appcast lists no versions".to_string());
+    }
+    Ok(versions)
+}
+
+// Older Windows-only sources, used when the appcast is unavailable
+async fn fetch_windows_legacy_version(client: &reqwest::Client, base_url: &str) -> Option<String> {
+    let base_url = base_url.trim_end_matches('/');
+
+    // Docker Desktop's update API, which is not always publicly accessible
+    if let Ok(response) = client
+        .get(format!("{}/api/updates/win/stable", base_url))
+        .header("Accept", "application/json")
+        .send()
+        .await
+    {
+        if response.status().is_success() {
+            if let Ok(json) = response.json::<serde_json::Value>().await {
+                if let Some(latest_version) = json["version"].as_str() {
+                    return Some(latest_version.to_string());
+                }
+            }
+        }
+    }
+
+    // Some mirrors include the version in the installer's headers
+    let response = client
+        .head(format!(
+            "{}/win/stable/Docker%20Desktop%20Installer.exe",
+            base_url
+        ))
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let latest_version = response.headers().get("x-version")?.to_str().ok()?;
+    Some(latest_version.to_string())
+}
+
+/// Versions offered by the platform's Docker Desktop update feed under `base_url`
+pub(crate) async fn fetch_desktop_versions(
+    client: &reqwest::Client,
+    base_url: &str,
+    platform: Platform,
+) -> Result<Vec<String>, String> {
+    match fetch_appcast_versions(client, base_url, platform).await {
+        Ok(versions) => Ok(versions),
+        Err(error) if platform == Platform::Windows => {
+            fetch_windows_legacy_version(client, base_url)
+                .await
+                .map(|version| vec![version])
+                .ok_or(error)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::test_support::TestServer;
+    use std::collections::HashMap;
+
+    /// Answers known programs with canned output and fails everything else
+    #[derive(Default)]
+    struct FakeRunner(HashMap<&'static str, &'static str>);
+
+    impl FakeRunner {
+        fn with(mut self, program: &'static str, output: &'static str) -> Self {
+            self.0.insert(program, output);
+            self
+        }
+    }
+
+    impl CommandRunner for FakeRunner {
+        fn run(&self, program: &str, _args: &[&str]) -> Option<String> {
+            self.0.get(program).map(|output| output.to_string())
+        }
+    }
+
+    fn detect(platform: Platform, runner: &FakeRunner) -> Option<String> {
+        DesktopEnvironment {
+            platform,
+            runner,
+            install_dir: PathBuf::from("/nonexistent/docker-desktop"),
+        }
+        .desktop_version()
+    }
+
+    #[test]
+    fn linux_reads_dpkg_then_rpm() {
+        let dpkg = FakeRunner::default().with("dpkg-query", "4.30.0-149282");
+        assert_eq!(detect(Platform::Linux, &dpkg).as_deref(), Some("4.30.0"));
+
+        let rpm = FakeRunner::default().with("rpm", "1:4.29.1-148742\n");
+        assert_eq!(detect(Platform::Linux, &rpm).as_deref(), Some("4.29.1"));
+
+        assert_eq!(detect(Platform::Linux, &FakeRunner::default()), None);
+    }
+
+    #[test]
+    fn windows_reads_registry_then_wmic() {
+        let reg = FakeRunner::default().with(
+            "reg",
+            "\r\nHKEY_LOCAL_MACHINE\\SOFTWARE\\Docker Inc.\\Docker Desktop\r\n    CurrentVersion    REG_SZ    4.30.0.149282\r\n",
+        );
+        assert_eq!(detect(Platform::Windows, &reg).as_deref(), Some("4.30.0"));
+
+        let wmic = FakeRunner::default().with("wmic", "\r\n\r\nVersion=4.28.0.139021\r\n\r\n");
+        assert_eq!(detect(Platform::Windows, &wmic).as_deref(), Some("4.28.0"));
+    }
+
+    #[test]
+    fn macos_reads_bundle_version() {
+        let defaults = FakeRunner::default().with("defaults", "4.30.0\n");
+        assert_eq!(
+            detect(Platform::MacOs, &defaults).as_deref(),
+            Some("4.30.0")
+        );
+    }
+
+    #[test]
+    fn components_version_takes_precedence() {
+        let install_dir =
+            std::env::temp_dir().join(format!("desktop-agent-test-{}", std::process::id()));
+        std::fs::create_dir_all(&install_dir).unwrap();
+        std::fs::write(
+            install_dir.join("componentsVersion.json"),
+            r#"{"appVersion": "4.31.1.153621", "Version": "4.31.1"}"#,
+        )
+        .unwrap();
+
+        let runner = FakeRunner::default().with("dpkg-query", "4.30.0-149282");
+        let version = DesktopEnvironment {
+            platform: Platform::Linux,
+            runner: &runner,
+            install_dir: install_dir.clone(),
+        }
+        .desktop_version();
+        std::fs::remove_dir_all(&install_dir).unwrap();
+        assert_eq!(version.as_deref(), Some("4.31.1"));
+    }
+
+    #[test]
+    fn appcast_lists_element_and_attribute_versions() {
+        let xml = r#"<rss><channel>
+            <item><sparkle:shortVersionString>4.30.0</sparkle:shortVersionString></item>
+            <item><enclosure sparkle:shortVersionString="4.29.0" url="x"/></item>
+        </channel></rss>"#;
+        assert_eq!(parse_appcast_versions(xml), vec!["4.30.0", "4.29.0"]);
+    }
+
+    #[tokio::test]
+    async fn fetches_appcast_for_platform() {
+        let server = TestServer::start(|_| {
+            (
+                200,
+                "<item><sparkle:shortVersionString>4.31.0</sparkle:shortVersionString></item>"
+                    .to_string(),
+            )
+        })
+        .await;
+
+        let versions =
+            fetch_desktop_versions(&reqwest::Client::new(), &server.url, Platform::MacOs).await;
+        assert_eq!(versions, Ok(vec!["4.31.0".to_string()]));
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(
+            requests[0].uri,
+            format!("/mac/main/{}/appcast.xml", feed_arch())
+        );
+    }
+
+    #[tokio::test]
+    async fn windows_falls_back_to_update_api() {
+        let server = TestServer::start(|request| match request.uri.as_str() {
+            "/api/updates/win/stable" => (200, r#"{"version": "4.30.0"}"#.to_string()),
+            _ => (404, String::new()),
+        })
+        .await;
+
+        let versions =
+            fetch_desktop_versions(&reqwest::Client::new(), &server.url, Platform::Windows).await;
+        assert_eq!(versions, Ok(vec!["4.30.0".to_string()]));
+        let api_request = &server.requests()[1];
+        assert_eq!(api_request.header("accept"), Some("application/json"));
+    }
+
+    #[tokio::test]
+    async fn empty_appcast_is_an_error() {
+        let server = TestServer::start(|_| (200, "<rss/>".to_string())).await;
+        let versions =
+            fetch_desktop_versions(&reqwest::Client::new(), &server.url, Platform::Linux).await;
+        assert!(versions.is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use bollard::models::ContainerSummaryStateEnum;
+use bollard::query_parameters::DataUsageOptions;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::docker::{get_active_client, DockerError};
+use super::sys_info::{get_storage_info, storage_size_to_bytes, StorageDevice};
+
+/// Disk usage of one kind of Docker object
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DiskUsageCategory {
+    pub count: usize,
+    /// Objects currently in use (images with containers, running containers, referenced volumes)
+    pub active: usize,
+    pub total_bytes: u64,
+    pub reclaimable_bytes: u64,
+}
+
+/// Breakdown returned by `get_docker_disk_usage`, equivalent to `docker system df`
+#[derive(Debug, Clone, Serialize)]
+pub struct DockerDiskUsage {
+    pub images: DiskUsageCategory,
+    pub containers: DiskUsageCategory,
+    pub volumes: DiskUsageCategory,
+    pub build_cache: DiskUsageCategory,
+    pub total_bytes: u64,
+    pub reclaimable_bytes: u64,
+    /// Docker's data root as reported by the daemon
+    pub data_root: Option<String>,
+    /// Host disk holding Docker's data, when it can be determined. Always
+    /// `None` for remote endpoints, whose data is not on this machine, and
+    /// for Docker Desktop on Linux, whose data is inside its VM.
+    pub storage_device: Option<StorageDevice>,
+    /// Share of that disk's capacity used by Docker
+    pub disk_percent: Option<f64>,
+    /// Share of that disk's capacity that pruning would free
+    pub reclaimable_disk_percent: Option<f64>,
+}
+
+fn non_negative(value: Option<i64>) -> u64 {
+    value.unwrap_or(0).max(0) as u64
+}
+
+/// Paths on the host that may hold Docker's data, most specific first. On
+/// Docker Desktop the data root lives inside a VM, so it says nothing about
+/// the host; on Windows and macOS fall back to where the VM disk image is
+/// stored, and on Linux leave the disk unknown.
+fn data_path_candidates(data_root: Option<&str>, in_vm: bool) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(data_root) = data_root.filter(|_| !in_vm) {
+        candidates.push(PathBuf::from(data_root));
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
+        candidates.push(Path::new(&local_app_data).join("Docker"));
+    }
+
+    #[cfg(target_os = "macos")]
+    if let Ok(home) = std::env::var("HOME") {
+        candidates.push(Path::new(&home).join("Library/Containers/com.docker.docker"));
+    }
+
+    candidates
+}
+
+/// Finds the storage device whose mount point is the longest prefix of `path`
+fn find_storage_device(devices: &[StorageDevice], path: &Path) -> Option<StorageDevice> {
+    devices
+        .iter()
+        .filter(|device| !device.mount_point.is_empty() && path.starts_with(&device.mount_point))
+        .max_by_key(|device| device.mount_point.len())
+        .cloned()
+}
+
+/// Tauri command to report Docker's disk usage and the host disk it lives on
+#[tauri::command]
+pub async fn get_docker_disk_usage() -> Result<DockerDiskUsage, DockerError> {
+    let (docker, endpoint) = get_active_client()?;
+
+    let df = docker.df(None::<DataUsageOptions>).await?;
+    let info = docker.info().await.ok();
+    let data_root = info.as_ref().and_then(|info| info.docker_root_dir.clone());
+    // Docker Desktop reports its VM as the operating system
+    let in_vm = info
+        .as_ref()
+        .and_then(|info| info.operating_system.as_deref())
+        == Some("Docker Desktop");
+
+    // Images: shared layers are counted once in `layers_size`; anything not
+    // backing a container can be reclaimed
+    let image_list = df.images.unwrap_or_default();
+    let image_total = df
+        .layers_size
+        .map(|size| size.max(0) as u64)
+        .unwrap_or_else(|| image_list.iter().map(|i| i.size.max(0) as u64).sum());
+    let active_images: Vec<_> = image_list.iter().filter(|i| i.containers > 0).collect();
+    let active_image_bytes: u64 = active_images.iter().map(|i| i.size.max(0) as u64).sum();
+    let images = DiskUsageCategory {
+        count: image_list.len(),
+        active: active_images.len(),
+        total_bytes: image_total,
+        reclaimable_bytes: image_total.saturating_sub(active_image_bytes),
+    };
+
+    // Containers: only the writable layer belongs to the container
+    let container_list = df.containers.unwrap_or_default();
+    let mut containers = DiskUsageCategory {
+        count: container_list.len(),
+        ..Default::default()
+    };
+    for container in &container_list {
+        let size = non_negative(container.size_rw);
+        containers.total_bytes += size;
+        if container.state == Some(ContainerSummaryStateEnum::RUNNING) {
+            containers.active += 1;
+        } else {
+            containers.reclaimable_bytes += size;
+        }
+    }
+
+    let volume_list = df.volumes.unwrap_or_default();
+    let mut volumes = DiskUsageCategory {
+        count: volume_list.len(),
+        ..Default::default()
+    };
+    for volume in &volume_list {
+        let (size, ref_count) = volume
+            .usage_data
+            .as_ref()
+            .map(|u| (u.size.max(0) as u64, u.ref_count))
+            .unwrap_or((0, 0));
+        volumes.total_bytes += size;
+        if ref_count > 0 {
+            volumes.active += 1;
+        } else {
+            volumes.reclaimable_bytes += size;
+        }
+    }
+
+    let cache_list = df.build_cache.unwrap_or_default();
+    let mut build_cache = DiskUsageCategory {
+        count: cache_list.len(),
+        ..Default::default()
+    };
+    for record in &cache_list {
+        let size = non_negative(record.size);
+        build_cache.total_bytes += size;
+        if record.in_use.unwrap_or(false) {
+            build_cache.active += 1;
+        } else if !record.shared.unwrap_or(false) {
+            build_cache.reclaimable_bytes += size;
+        }
+    }
+
+    let categories = [&images, &containers, &volumes, &build_cache];
+    let total_bytes: u64 = categories.iter().map(|c| c.total_bytes).sum();
+    let reclaimable_bytes: u64 = categories.iter().map(|c| c.reclaimable_bytes).sum();
+
+    let storage_device = if endpoint.is_local() {
+        let candidates = data_path_candidates(data_root.as_deref(), in_vm);
+        // Listing disks is blocking
+        tauri::async_runtime::spawn_blocking(move || {
+            let devices = get_storage_info()
+                .map(|info| info.devices)
+                .unwrap_or_default();
+            candidates
+                .iter()
+                .find_map(|path| find_storage_device(&devices, path))
+        })
+        .await
+        .unwrap_or_else(|error| {
+            tracing::warn!(%error, "Could not read host disks");
+            None
+        })
+    } else {
+        None
+    };
+
+    let device_bytes = storage_device
+        .as_ref()
+        .map(|device| storage_size_to_bytes(device.total_size, &device.unit))
+        .filter(|bytes| *bytes > 0);
+    let percent_of_device = |bytes: u64| device_bytes.map(|d| bytes as f64 / d as f64 * 100.0);
+
+    Ok(DockerDiskUsage {
+        images,
+        containers,
+        volumes,
+        build_cache,
+        total_bytes,
+        reclaimable_bytes,
+        data_root,
+        disk_percent: percent_of_device(total_bytes),
+        reclaimable_disk_percent: percent_of_device(reclaimable_bytes),
+        storage_device,
+    })
+}
@@ -0,0 +1,610 @@
+use bollard::models::SystemVersion;
+use bollard::{Docker, API_DEFAULT_VERSION};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::Emitter;
+
+use super::docker::{
+    endpoint_status, spawn_endpoint_monitoring, stop_event_monitoring, DockerError, DockerState,
+    DockerStatus,
+};
+use super::docker_exec;
+use super::docker_health;
+use super::docker_stats;
+
+// Request timeout in seconds handed to bollard, matching its own defaults
+const CLIENT_TIMEOUT_SECS: u64 = 120;
+
+#[cfg(unix)]
+const DEFAULT_HOST: &str = "unix:///var/run/docker.sock";
+#[cfg(windows)]
+const DEFAULT_HOST: &str = "npipe:////./pipe/docker_engine";
+
+const DEFAULT_ENDPOINT: &str = "default";
+const ENVIRONMENT_ENDPOINT: &str = "DOCKER_HOST";
+
+/// Where an endpoint definition came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointSource {
+    Default,
+    Environment,
+    Context,
+    User,
+}
+
+/// Which engine answered on an endpoint's socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineFlavor {
+    Docker,
+    DockerRootless,
+    DockerDesktop,
+    Podman,
+}
+
+impl EngineFlavor {
+    /// Identifies the engine from the components listed in its `version` response
+    pub fn from_version(version: &SystemVersion) -> Self {
+        let components: Vec<String> = version
+            .components
+            .iter()
+            .flatten()
+            .map(|c| c.name.to_lowercase())
+            .collect();
+        let platform = version
+            .platform
+            .as_ref()
+            .map(|p| p.name.to_lowercase())
+            .unwrap_or_default();
+
+        if components.iter().any(|name| name.contains("podman")) || platform.contains("podman") {
+            EngineFlavor::Podman
+        } else if platform.contains("docker desktop") {
+            EngineFlavor::DockerDesktop
+        } else if components.iter().any(|name| name == "rootlesskit") {
+            // Rootless Docker reports the RootlessKit wrapper as a component
+            EngineFlavor::DockerRootless
+        } else {
+            EngineFlavor::Docker
+        }
+    }
+}
+
+/// Client certificates for a TLS-protected TCP endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointTls {
+    pub ca_cert: String,
+    pub cert: String,
+    pub key: String,
+}
+
+/// A Docker daemon the agent can monitor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerEndpoint {
+    pub name: String,
+    /// Daemon address as used by the Docker CLI: `unix://`, `npipe://`, `tcp://` or `https://`
+    pub host: String,
+    pub tls: Option<EndpointTls>,
+    pub source: EndpointSource,
+    pub description: Option<String>,
+}
+
+impl DockerEndpoint {
+    /// Whether the daemon runs on this machine, so local Docker Desktop details apply
+    pub fn is_local(&self) -> bool {
+        self.host.starts_with("unix://") || self.host.starts_with("npipe://")
+    }
+}
+
+/// An endpoint together with its current connection status
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointInfo {
+    #[serde(flatten)]
+    pub endpoint: DockerEndpoint,
+    pub active: bool,
+    pub status: DockerStatus,
+}
+
+struct RegisteredEndpoint {
+    state: Arc<Mutex<DockerState>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+struct EndpointRegistry {
+    // Kept in discovery order so the frontend can list them predictably
+    endpoints: Vec<(String, RegisteredEndpoint)>,
+    active: String,
+}
+
+impl EndpointRegistry {
+    fn get(&self, name: &str) -> Option<&RegisteredEndpoint> {
+        self.endpoints
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, endpoint)| endpoint)
+    }
+}
+
+static ENDPOINT_REGISTRY: std::sync::OnceLock<Arc<Mutex<EndpointRegistry>>> =
+    std::sync::OnceLock::new();
+
+fn get_registry() -> Arc<Mutex<EndpointRegistry>> {
+    ENDPOINT_REGISTRY
+        .get_or_init(|| {
+            let (endpoints, active) = discover_endpoints();
+            let endpoints = endpoints
+                .into_iter()
+                .map(|endpoint| {
+                    let name = endpoint.name.clone();
+                    let registered = RegisteredEndpoint {
+                        state: Arc::new(Mutex::new(DockerState::new(endpoint))),
+                        tasks: Vec::new(),
+                    };
+                    (name, registered)
+                })
+                .collect();
+            Arc::new(Mutex::new(EndpointRegistry { endpoints, active }))
+        })
+        .clone()
+}
+
+// State of the endpoint that commands and the frontend currently operate on
+pub(crate) fn get_active_state() -> Arc<Mutex<DockerState>> {
+    let registry = get_registry();
+    let registry_guard = registry.lock().unwrap();
+    registry_guard
+        .get(&registry_guard.active)
+        .or_else(|| registry_guard.endpoints.first().map(|(_, e)| e))
+        .map(|e| Arc::clone(&e.state))
+        .expect("the endpoint registry always holds at least one endpoint")
+}
+
+// State of every registered endpoint, active or not
+pub(crate) fn all_states() -> Vec<Arc<Mutex<DockerState>>> {
+    let registry = get_registry();
+    let registry_guard = registry.lock().unwrap();
+    registry_guard
+        .endpoints
+        .iter()
+        .map(|(_, e)| Arc::clone(&e.state))
+        .collect()
+}
+
+pub(crate) fn is_active_endpoint(name: &str) -> bool {
+    let registry = get_registry();
+    let registry_guard = registry.lock().unwrap();
+    registry_guard.active == name
+}
+
+// Start a monitoring loop for every registered endpoint that does not have one yet
+pub(crate) fn start_all_endpoint_monitoring(app_handle: &tauri::AppHandle) {
+    let registry = get_registry();
+    let mut registry_guard = registry.lock().unwrap();
+    for (_, endpoint) in registry_guard.endpoints.iter_mut() {
+        if endpoint.tasks.is_empty() {
+            endpoint.tasks = spawn_endpoint_monitoring(Arc::clone(&endpoint.state), app_handle);
+        }
+    }
+}
+
+/// Connects to an endpoint without contacting the daemon yet
+pub(crate) fn connect(endpoint: &DockerEndpoint) -> Result<Docker, DockerError> {
+    let host = endpoint.host.as_str();
+    let result = if host.starts_with("unix://") || host.starts_with("npipe://") {
+        Docker::connect_with_local(host, CLIENT_TIMEOUT_SECS, API_DEFAULT_VERSION)
+    } else if host.starts_with("tcp://")
+        || host.starts_with("http://")
+        || host.starts_with("https://")
+    {
+        match &endpoint.tls {
+            Some(tls) => Docker::connect_with_ssl(
+                host,
+                Path::new(&tls.key),
+                Path::new(&tls.cert),
+                Path::new(&tls.ca_cert),
+                CLIENT_TIMEOUT_SECS,
+                API_DEFAULT_VERSION,
+            ),
+            None => Docker::connect_with_http(host, CLIENT_TIMEOUT_SECS, API_DEFAULT_VERSION),
+        }
+    } else {
+        return Err(DockerError::InvalidInput(format!(
+            "unsupported Docker host {}",
+            host
+        )));
+    };
+
+    result.map_err(|e| DockerError::ConnectionFailed(e.to_string()))
+}
+
+/// Local sockets the default endpoint probes, in order of preference: the
+/// system daemon, rootless Docker, Docker Desktop for Linux, then Podman's
+/// Docker-compatible API as a user and as root
+#[cfg(unix)]
+fn local_socket_candidates() -> Vec<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .or_else(|| {
+            // The usual runtime directory when XDG_RUNTIME_DIR is not exported
+            std::fs::metadata("/proc/self")
+                .ok()
+                .map(|meta| PathBuf::from(format!("/run/user/{}", meta.uid())))
+        });
+
+    let mut paths = vec![PathBuf::from("/var/run/docker.sock")];
+    if let Some(runtime_dir) = &runtime_dir {
+        paths.push(runtime_dir.join("docker.sock"));
+    }
+    if let Some(home) = home_dir() {
+        paths.push(home.join(".docker/desktop/docker.sock"));
+        paths.push(home.join(".docker/run/docker.sock"));
+    }
+    if let Some(runtime_dir) = &runtime_dir {
+        paths.push(runtime_dir.join("podman/podman.sock"));
+    }
+    paths.push(PathBuf::from("/run/podman/podman.sock"));
+
+    paths
+        .iter()
+        .map(|path| format!("unix://{}", path.display()))
+        .collect()
+}
+
+#[cfg(windows)]
+fn local_socket_candidates() -> Vec<String> {
+    vec![
+        DEFAULT_HOST.to_string(),
+        "npipe:////./pipe/podman-machine-default".to_string(),
+    ]
+}
+
+fn socket_exists(host: &str) -> bool {
+    match host.strip_prefix("unix://") {
+        Some(path) => Path::new(path).exists(),
+        None => true,
+    }
+}
+
+/// Connects to an endpoint and asks the daemon for its version. The default
+/// endpoint tries each known local socket in turn and uses the first that
+/// answers. Returns the client, the version response and the host that answered.
+pub(crate) async fn connect_and_probe(
+    endpoint: &DockerEndpoint,
+    timeout: Duration,
+) -> Result<(Docker, SystemVersion, String), DockerError> {
+    let mut hosts = if endpoint.source == EndpointSource::Default {
+        local_socket_candidates()
+            .into_iter()
+            .filter(|host| socket_exists(host))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    if hosts.is_empty() {
+        hosts.push(endpoint.host.clone());
+    }
+
+    let mut first_error = None;
+    for host in hosts {
+        let probe = DockerEndpoint {
+            host: host.clone(),
+            ..endpoint.clone()
+        };
+        let error = match connect(&probe) {
+            Ok(docker) => match tokio::time::timeout(timeout, docker.version()).await {
+                Ok(Ok(version)) => return Ok((docker, version, host)),
+                Ok(Err(_)) => DockerError::NotRunning,
+                Err(_) => DockerError::Timeout,
+            },
+            Err(error) => error,
+        };
+        first_error.get_or_insert(error);
+    }
+
+    Err(first_error.unwrap_or(DockerError::NotRunning))
+}
+
+fn validate_host(host: &str) -> Result<(), DockerError> {
+    let supported = ["unix://", "npipe://", "tcp://", "http://", "https://"];
+    if supported.iter().any(|scheme| host.starts_with(scheme)) {
+        Ok(())
+    } else {
+        Err(DockerError::InvalidInput(format!(
+            "unsupported Docker host {}, expected one of {}",
+            host,
+            supported.join(", ")
+        )))
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// The Docker CLI configuration directory (`$DOCKER_CONFIG` or `~/.docker`)
+fn docker_config_dir() -> Option<PathBuf> {
+    std::env::var_os("DOCKER_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".docker")))
+}
+
+/// TLS material in `dir` using the Docker CLI file names, if all of it exists
+fn tls_from_dir(dir: &Path) -> Option<EndpointTls> {
+    let file = |name: &str| {
+        let path = dir.join(name);
+        path.is_file().then(|| path.to_string_lossy().into_owned())
+    };
+    Some(EndpointTls {
+        ca_cert: file("ca.pem")?,
+        cert: file("cert.pem")?,
+        key: file("key.pem")?,
+    })
+}
+
+/// Endpoint described by `DOCKER_HOST`, `DOCKER_TLS_VERIFY` and `DOCKER_CERT_PATH`
+fn environment_endpoint() -> Option<DockerEndpoint> {
+    let host = std::env::var("DOCKER_HOST")
+        .ok()
+        .filter(|h| !h.is_empty())?;
+    let tls = std::env::var_os("DOCKER_TLS_VERIFY")
+        .filter(|v| !v.is_empty())
+        .and_then(|_| {
+            std::env::var_os("DOCKER_CERT_PATH")
+                .map(PathBuf::from)
+                .or_else(docker_config_dir)
+        })
+        .and_then(|dir| tls_from_dir(&dir));
+
+    Some(DockerEndpoint {
+        name: ENVIRONMENT_ENDPOINT.to_string(),
+        host,
+        tls,
+        source: EndpointSource::Environment,
+        description: Some("From the DOCKER_HOST environment variable".to_string()),
+    })
+}
+
+/// Endpoints of the Docker CLI contexts stored under `<config>/contexts`. Each
+/// context has a `meta/<id>/meta.json` and optional TLS files in `tls/<id>/docker`.
+fn context_endpoints(config_dir: &Path) -> Vec<DockerEndpoint> {
+    let contexts_dir = config_dir.join("contexts");
+    let entries = match std::fs::read_dir(contexts_dir.join("meta")) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut endpoints: Vec<DockerEndpoint> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let meta = std::fs::read_to_string(entry.path().join("meta.json")).ok()?;
+            let meta: serde_json::Value = serde_json::from_str(&meta).ok()?;
+            let name = meta["Name"].as_str()?.to_string();
+            let host = meta["Endpoints"]["docker"]["Host"].as_str()?.to_string();
+            let tls = tls_from_dir(
+                &contexts_dir
+                    .join("tls")
+                    .join(entry.file_name())
+                    .join("docker"),
+            );
+
+            Some(DockerEndpoint {
+                name,
+                host,
+                tls,
+                source: EndpointSource::Context,
+                description: meta["Metadata"]["Description"]
+                    .as_str()
+                    .filter(|d| !d.is_empty())
+                    .map(str::to_string),
+            })
+        })
+        .filter(|endpoint| validate_host(&endpoint.host).is_ok())
+        .collect();
+
+    endpoints.sort_by(|a, b| a.name.cmp(&b.name));
+    endpoints
+}
+
+/// The context selected with `docker context use`, if any
+fn current_context(config_dir: &Path) -> Option<String> {
+    let config = std::fs::read_to_string(config_dir.join("config.json")).ok()?;
+    let config: serde_json::Value = serde_json::from_str(&config).ok()?;
+    config["currentContext"].as_str().map(str::to_string)
+}
+
+/// Builds the initial endpoint list and picks the active endpoint the same way
+/// the Docker CLI does: `DOCKER_HOST`, then `DOCKER_CONTEXT`, then the current
+/// context, then the default socket.
+fn discover_endpoints() -> (Vec<DockerEndpoint>, String) {
+    let mut endpoints = vec![DockerEndpoint {
+        name: DEFAULT_ENDPOINT.to_string(),
+        host: DEFAULT_HOST.to_string(),
+        tls: None,
+        source: EndpointSource::Default,
+        description: Some("Local Docker daemon, discovered automatically".to_string()),
+    }];
+
+    let environment = environment_endpoint();
+    let config_dir = docker_config_dir();
+
+    if let Some(endpoint) = &environment {
+        endpoints.push(endpoint.clone());
+    }
+    if let Some(config_dir) = &config_dir {
+        for endpoint in context_endpoints(config_dir) {
+            // The CLI always resolves "default" to the local daemon itself
+            if !endpoints.iter().any(|e| e.name == endpoint.name) {
+                endpoints.push(endpoint);
+            }
+        }
+    }
+
+    let requested = if environment.is_some() {
+        Some(ENVIRONMENT_ENDPOINT.to_string())
+    } else {
+        std::env::var("DOCKER_CONTEXT")
+            .ok()
+            .or_else(|| config_dir.as_deref().and_then(current_context))
+    };
+    let active = requested
+        .filter(|name| endpoints.iter().any(|e| &e.name == name))
+        .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
+
+    (endpoints, active)
+}
+
+fn endpoint_info(state: &Arc<Mutex<DockerState>>, active: &str) -> EndpointInfo {
+    let (endpoint, status) = endpoint_status(state);
+    EndpointInfo {
+        active: endpoint.name == active,
+        endpoint,
+        status,
+    }
+}
+
+/// Tauri command to list every known endpoint with its connection status
+#[tauri::command]
+pub async fn list_docker_endpoints() -> Result<Vec<EndpointInfo>, DockerError> {
+    let states = all_states();
+    let active = {
+        let registry = get_registry();
+        let registry_guard = registry.lock().unwrap();
+        registry_guard.active.clone()
+    };
+
+    Ok(states
+        .iter()
+        .map(|state| endpoint_info(state, &active))
+        .collect())
+}
+
+/// Tauri command to register a daemon by address and start monitoring it
+#[tauri::command]
+pub async fn add_docker_endpoint(
+    app_handle: tauri::AppHandle,
+    name: String,
+    host: String,
+    tls: Option<EndpointTls>,
+    description: Option<String>,
+) -> Result<EndpointInfo, DockerError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(DockerError::InvalidInput(
+            "endpoint name must not be empty".to_string(),
+        ));
+    }
+    validate_host(&host)?;
+
+    let endpoint = DockerEndpoint {
+        name: name.clone(),
+        host,
+        tls,
+        source: EndpointSource::User,
+        description,
+    };
+    let state = Arc::new(Mutex::new(DockerState::new(endpoint)));
+
+    let active = {
+        let registry = get_registry();
+        let mut registry_guard = registry.lock().unwrap();
+        if registry_guard.get(&name).is_some() {
+            return Err(DockerError::Conflict(format!(
+                "endpoint {} already exists",
+                name
+            )));
+        }
+        let tasks = spawn_endpoint_monitoring(Arc::clone(&state), &app_handle);
+        registry_guard.endpoints.push((
+            name,
+            RegisteredEndpoint {
+                state: Arc::clone(&state),
+                tasks,
+            },
+        ));
+        registry_guard.active.clone()
+    };
+
+    Ok(endpoint_info(&state, &active))
+}
+
+/// Tauri command to stop monitoring an endpoint and forget it. The active
+/// endpoint cannot be removed.
+#[tauri::command]
+pub async fn remove_docker_endpoint(name: String) -> Result<(), DockerError> {
+    let removed = {
+        let registry = get_registry();
+        let mut registry_guard = registry.lock().unwrap();
+        if registry_guard.active == name {
+            return Err(DockerError::Conflict(format!(
+                "endpoint {} is active, switch to another endpoint first",
+                name
+            )));
+        }
+        let index = registry_guard
+            .endpoints
+            .iter()
+            .position(|(n, _)| n == &name)
+            .ok_or_else(|| DockerError::NotFound(format!("endpoint {}", name)))?;
+        registry_guard.endpoints.remove(index).1
+    };
+
+    for task in removed.tasks {
+        task.abort();
+    }
+    stop_event_monitoring(&removed.state);
+    Ok(())
+}
+
+/// Tauri command to make another endpoint the one all Docker commands use
+#[tauri::command]
+pub async fn switch_docker_endpoint(
+    app_handle: tauri::AppHandle,
+    name: String,
+) -> Result<EndpointInfo, DockerError> {
+    let state = {
+        let registry = get_registry();
+        let registry_guard = registry.lock().unwrap();
+        let endpoint = registry_guard
+            .get(&name)
+            .ok_or_else(|| DockerError::NotFound(format!("endpoint {}", name)))?;
+        if registry_guard.active == name {
+            return Ok(endpoint_info(&endpoint.state, &name));
+        }
+        Arc::clone(&endpoint.state)
+    };
+
+    // Per-container tracking belongs to the previous endpoint's daemon
+    docker_stats::detach_all_container_stats();
+    docker_exec::close_all_exec_sessions(&app_handle);
+    docker_health::clear_container_health();
+
+    {
+        let registry = get_registry();
+        let mut registry_guard = registry.lock().unwrap();
+        registry_guard.active = name.clone();
+    }
+
+    let info = endpoint_info(&state, &name);
+    if let Err(error) = app_handle.emit("docker-status-updated", info.status.clone()) {
+        tracing::warn!(%error, "Could not emit docker-status-updated");
+    }
+
+    if info.status.is_running {
+        if let Err(error) = docker_stats::attach_running_containers(&app_handle).await {
+            tracing::warn!(%error, "Could not attach stats to running containers");
+        }
+        if let Err(error) = docker_health::seed_container_health(&app_handle).await {
+            tracing::warn!(%error, "Could not read container health");
+        }
+    }
+
+    Ok(info)
+}
@@ -0,0 +1,345 @@
+use bollard::exec::{StartExecOptions, StartExecResults};
+use bollard::models::ExecConfig;
+use bollard::query_parameters::ResizeExecOptions;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::async_runtime::JoinHandle;
+use tauri::Emitter;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+
+use super::docker::{get_docker_client, get_docker_state, DockerError, DockerEvent};
+
+/// Options accepted by `start_exec_session`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExecSessionOptions {
+    /// Command to run (defaults to `/bin/sh`)
+    #[serde(default)]
+    pub cmd: Vec<String>,
+    pub user: Option<String>,
+    /// Environment variables as "KEY=value"
+    #[serde(default)]
+    pub env: Vec<String>,
+    pub working_dir: Option<String>,
+    /// Allocate a pseudo-terminal (defaults to true)
+    pub tty: Option<bool>,
+    pub rows: Option<u16>,
+    pub cols: Option<u16>,
+}
+
+/// Public description of a running exec session
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecSessionInfo {
+    pub session_id: String,
+    pub exec_id: String,
+    pub container: String,
+    pub cmd: Vec<String>,
+    pub tty: bool,
+    pub started_at: String,
+}
+
+/// Terminal output emitted as `docker-exec-output`
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecOutput {
+    pub session_id: String,
+    pub data: Vec<u8>,
+}
+
+/// Emitted as `docker-exec-exit` when a session ends for any reason
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecExit {
+    pub session_id: String,
+    pub exit_code: Option<i64>,
+    pub reason: String,
+}
+
+/// An exec session tracked in `DockerState`
+pub(crate) struct ExecSession {
+    info: ExecSessionInfo,
+    /// Container id as reported by the daemon, used to match die events
+    container_id: String,
+    stdin: mpsc::UnboundedSender<Vec<u8>>,
+    task: JoinHandle<()>,
+}
+
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+fn take_session(session_id: &str) -> Option<ExecSession> {
+    let state = get_docker_state();
+    let mut state_guard = state.lock().unwrap();
+    state_guard.exec_sessions.remove(session_id)
+}
+
+fn emit_exit(
+    app_handle: &tauri::AppHandle,
+    session_id: &str,
+    exit_code: Option<i64>,
+    reason: &str,
+) {
+    if let Err(error) = app_handle.emit(
+        "docker-exec-exit",
+        ExecExit {
+            session_id: session_id.to_string(),
+            exit_code,
+            reason: reason.to_string(),
+        },
+    ) {
+        tracing::warn!(%error, "Could not emit docker-exec-exit");
+    }
+}
+
+/// Ends every session matching `predicate` and tells the frontend why
+fn close_sessions_where(
+    app_handle: &tauri::AppHandle,
+    reason: &str,
+    predicate: impl Fn(&ExecSession) -> bool,
+) {
+    let closed: Vec<ExecSession> = {
+        let state = get_docker_state();
+        let mut state_guard = state.lock().unwrap();
+        let ids: Vec<String> = state_guard
+            .exec_sessions
+            .iter()
+            .filter(|(_, session)| predicate(session))
+            .map(|(id, _)| id.clone())
+            .collect();
+        ids.iter()
+            .filter_map(|id| state_guard.exec_sessions.remove(id))
+            .collect()
+    };
+
+    for session in closed {
+        session.task.abort();
+        emit_exit(app_handle, &session.info.session_id, None, reason);
+    }
+}
+
+/// Closes sessions whose container stopped or was removed
+pub(crate) fn handle_container_event(app_handle: &tauri::AppHandle, event: &DockerEvent) {
+    if event.event_type != "container" || !matches!(event.action.as_str(), "die" | "destroy") {
+        return;
+    }
+    if let Some(container_id) = event.actor_id.as_deref() {
+        close_sessions_where(app_handle, "container stopped", |session| {
+            session.container_id == container_id
+        });
+    }
+}
+
+/// Closes every session, e.g. after the Docker connection is lost
+pub(crate) fn close_all_exec_sessions(app_handle: &tauri::AppHandle) {
+    close_sessions_where(app_handle, "Docker connection lost", |_| true);
+}
+
+/// Tauri command to start an interactive process in a container. Output is
+/// emitted as `docker-exec-output` and the end of the process as `docker-exec-exit`.
+#[tauri::command]
+pub async fn start_exec_session(
+    app_handle: tauri::AppHandle,
+    container: String,
+    options: ExecSessionOptions,
+) -> Result<ExecSessionInfo, DockerError> {
+    let docker = get_docker_client()?;
+
+    let cmd = if options.cmd.is_empty() {
+        vec!["/bin/sh".to_string()]
+    } else {
+        options.cmd
+    };
+    let tty = options.tty.unwrap_or(true);
+    let console_size = options
+        .rows
+        .zip(options.cols)
+        .map(|(rows, cols)| vec![rows as usize, cols as usize]);
+
+    let container_id = docker
+        .inspect_container(
+            &container,
+            None::<bollard::query_parameters::InspectContainerOptions>,
+        )
+        .await?
+        .id
+        .unwrap_or_else(|| container.clone());
+
+    let exec = docker
+        .create_exec(
+            &container_id,
+            ExecConfig {
+                attach_stdin: Some(true),
+                attach_stdout: Some(true),
+                attach_stderr: Some(true),
+                tty: Some(tty),
+                console_size,
+                cmd: Some(cmd.clone()),
+                user: options.user,
+                env: (!options.env.is_empty()).then_some(options.env),
+                working_dir: options.working_dir,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let (mut output, mut input) = match docker
+        .start_exec(
+            &exec.id,
+            Some(StartExecOptions {
+                detach: false,
+                tty,
+                output_capacity: None,
+            }),
+        )
+        .await?
+    {
+        StartExecResults::Attached { output, input } => (output, input),
+        StartExecResults::Detached => {
+            return Err(DockerError::RequestFailed(
+                "exec started detached".to_string(),
+            ))
+        }
+    };
+
+    let session_id = format!("exec-{}", NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed));
+    let (stdin, mut stdin_rx) = mpsc::unbounded_channel::<Vec<u8>>();
+
+    let info = ExecSessionInfo {
+        session_id: session_id.clone(),
+        exec_id: exec.id.clone(),
+        container,
+        cmd,
+        tty,
+        started_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    // Hold the state lock until the session is stored so a process that exits
+    // immediately cannot try to remove itself before it was registered
+    let state = get_docker_state();
+    let mut state_guard = state.lock().unwrap();
+
+    let id = session_id.clone();
+    let exec_id = exec.id.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                chunk = output.next() => match chunk {
+                    Some(Ok(chunk)) => {
+                        if let Err(error) = app_handle.emit(
+                            "docker-exec-output",
+                            ExecOutput {
+                                session_id: id.clone(),
+                                data: chunk.into_bytes().to_vec(),
+                            },
+                        ) {
+                            tracing::warn!(%error, "Could not emit docker-exec-output");
+                        }
+                    }
+                    _ => break,
+                },
+                data = stdin_rx.recv() => match data {
+                    Some(data) => {
+                        if input.write_all(&data).await.is_err() {
+                            break;
+                        }
+                        let _ = input.flush().await;
+                    }
+                    None => break,
+                },
+            }
+        }
+
+        // Only report the exit if the session was not already closed elsewhere
+        if take_session(&id).is_some() {
+            let exit_code = docker
+                .inspect_exec(&exec_id)
+                .await
+                .ok()
+                .and_then(|details| details.exit_code);
+            emit_exit(&app_handle, &id, exit_code, "process exited");
+        }
+    });
+
+    state_guard.exec_sessions.insert(
+        session_id,
+        ExecSession {
+            info: info.clone(),
+            container_id,
+            stdin,
+            task,
+        },
+    );
+    drop(state_guard);
+
+    Ok(info)
+}
+
+/// Sends keyboard input to a session
+#[tauri::command]
+pub async fn write_exec_session(session_id: String, data: String) -> Result<(), DockerError> {
+    let state = get_docker_state();
+    let state_guard = state.lock().unwrap();
+    let session = state_guard
+        .exec_sessions
+        .get(&session_id)
+        .ok_or_else(|| DockerError::NotFound(format!("exec session {}", session_id)))?;
+
+    session
+        .stdin
+        .send(data.into_bytes())
+        .map_err(|_| DockerError::ConnectionLost)
+}
+
+/// Resizes the session's pseudo-terminal to match the frontend terminal
+#[tauri::command]
+pub async fn resize_exec_session(
+    session_id: String,
+    rows: u16,
+    cols: u16,
+) -> Result<(), DockerError> {
+    let exec_id = {
+        let state = get_docker_state();
+        let state_guard = state.lock().unwrap();
+        state_guard
+            .exec_sessions
+            .get(&session_id)
+            .map(|session| session.info.exec_id.clone())
+            .ok_or_else(|| DockerError::NotFound(format!("exec session {}", session_id)))?
+    };
+
+    let docker = get_docker_client()?;
+    docker
+        .resize_exec(
+            &exec_id,
+            ResizeExecOptions {
+                h: rows as i32,
+                w: cols as i32,
+            },
+        )
+        .await?;
+    Ok(())
+}
+
+/// Detaches from a session. The process keeps running in the container if it
+/// does not exit when its stdin closes.
+#[tauri::command]
+pub async fn close_exec_session(
+    app_handle: tauri::AppHandle,
+    session_id: String,
+) -> Result<(), DockerError> {
+    let session = take_session(&session_id)
+        .ok_or_else(|| DockerError::NotFound(format!("exec session {}", session_id)))?;
+
+    session.task.abort();
+    emit_exit(&app_handle, &session_id, None, "closed");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_exec_sessions() -> Result<Vec<ExecSessionInfo>, DockerError> {
+    let state = get_docker_state();
+    let state_guard = state.lock().unwrap();
+    Ok(state_guard
+        .exec_sessions
+        .values()
+        .map(|session| session.info.clone())
+        .collect())
+}
@@ -0,0 +1,334 @@
+use bollard::query_parameters::{InspectContainerOptions, ListContainersOptions};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+use super::docker::{
+    get_connection_timeout, get_docker_client, get_monitoring_config, DockerError, DockerEvent,
+};
+use super::notifications;
+
+// Maximum number of alerts kept for `get_container_alerts`
+const MAX_ALERTS: usize = 200;
+
+// A non-zero exit this soon after a kill is treated as a deliberate stop
+const KILL_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Why a container alert was raised
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum ContainerAlertKind {
+    Unhealthy,
+    NonZeroExit { exit_code: i64 },
+    RestartLoop { restarts: usize, window_secs: u64 },
+}
+
+/// Emitted as `docker-container-alert` and returned by `get_container_alerts`
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerAlert {
+    pub id: u64,
+    pub container_id: String,
+    pub container_name: String,
+    #[serde(flatten)]
+    pub kind: ContainerAlertKind,
+    pub message: String,
+    pub restart_count: i64,
+    pub timestamp: String,
+}
+
+/// Workload-level health of a container as tracked from inspect data and events
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ContainerHealth {
+    pub container_id: String,
+    pub name: String,
+    /// "healthy", "unhealthy", "starting" or `None` without a health check
+    pub health: Option<String>,
+    pub restart_count: i64,
+    pub last_exit_code: Option<i64>,
+    #[serde(skip)]
+    recent_starts: VecDeque<Instant>,
+    #[serde(skip)]
+    last_kill: Option<Instant>,
+    #[serde(skip)]
+    restart_loop_alerted_at: Option<Instant>,
+}
+
+#[derive(Default)]
+struct HealthState {
+    containers: HashMap<String, ContainerHealth>,
+    alerts: VecDeque<ContainerAlert>,
+}
+
+static HEALTH_STATE: std::sync::OnceLock<Arc<Mutex<HealthState>>> = std::sync::OnceLock::new();
+static NEXT_ALERT_ID: AtomicU64 = AtomicU64::new(1);
+
+fn get_health_state() -> Arc<Mutex<HealthState>> {
+    HEALTH_STATE
+        .get_or_init(|| Arc::new(Mutex::new(HealthState::default())))
+        .clone()
+}
+
+fn raise_alert(
+    app_handle: &tauri::AppHandle,
+    container: &ContainerHealth,
+    kind: ContainerAlertKind,
+) {
+    let message = match &kind {
+        ContainerAlertKind::Unhealthy => format!("{} is unhealthy", container.name),
+        ContainerAlertKind::NonZeroExit { exit_code } => {
+            format!("{} exited with code {}", container.name, exit_code)
+        }
+        ContainerAlertKind::RestartLoop {
+            restarts,
+            window_secs,
+        } => format!(
+            "{} restarted {} times in {} minutes",
+            container.name,
+            restarts,
+            window_secs / 60
+        ),
+    };
+
+    let alert = ContainerAlert {
+        id: NEXT_ALERT_ID.fetch_add(1, Ordering::Relaxed),
+        container_id: container.container_id.clone(),
+        container_name: container.name.clone(),
+        kind,
+        message,
+        restart_count: container.restart_count,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    {
+        let state = get_health_state();
+        let mut state_guard = state.lock().unwrap();
+        if state_guard.alerts.len() >= MAX_ALERTS {
+            state_guard.alerts.pop_front();
+        }
+        state_guard.alerts.push_back(alert.clone());
+    }
+
+    notifications::notify_container_alert(&alert);
+    if let Err(error) = app_handle.emit("docker-container-alert", alert) {
+        tracing::warn!(%error, "Could not emit docker-container-alert");
+    }
+}
+
+/// Reads health and restart count from the daemon for one container
+async fn inspect_health(container_id: &str) -> Result<(String, Option<String>, i64), DockerError> {
+    let docker = get_docker_client()?;
+    let details = tokio::time::timeout(
+        get_connection_timeout(),
+        docker.inspect_container(container_id, None::<InspectContainerOptions>),
+    )
+    .await
+    .map_err(|_| DockerError::Timeout)??;
+
+    let name = details
+        .name
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+    let health = details
+        .state
+        .and_then(|s| s.health)
+        .and_then(|h| h.status)
+        .map(|s| s.to_string())
+        .filter(|s| !s.is_empty() && s != "none");
+
+    Ok((name, health, details.restart_count.unwrap_or(0)))
+}
+
+/// Records the current health of running containers and alerts on any that
+/// are already unhealthy when the connection is established
+pub(crate) async fn seed_container_health(
+    app_handle: &tauri::AppHandle,
+) -> Result<(), DockerError> {
+    let docker = get_docker_client()?;
+    let containers = tokio::time::timeout(
+        get_connection_timeout(),
+        docker.list_containers(None::<ListContainersOptions>),
+    )
+    .await
+    .map_err(|_| DockerError::Timeout)??;
+
+    for id in containers.into_iter().filter_map(|c| c.id) {
+        let (name, health, restart_count) = match inspect_health(&id).await {
+            Ok(details) => details,
+            Err(_) => continue,
+        };
+
+        let (entry, newly_unhealthy) = {
+            let state = get_health_state();
+            let mut state_guard = state.lock().unwrap();
+            let entry = state_guard.containers.entry(id.clone()).or_default();
+            let newly_unhealthy = health.as_deref() == Some("unhealthy") && entry.health != health;
+            entry.container_id = id;
+            entry.name = name;
+            entry.health = health;
+            entry.restart_count = restart_count;
+            (entry.clone(), newly_unhealthy)
+        };
+
+        if newly_unhealthy {
+            raise_alert(app_handle, &entry, ContainerAlertKind::Unhealthy);
+        }
+    }
+
+    Ok(())
+}
+
+/// Forgets tracked containers, e.g. when switching to another endpoint. The
+/// alert history is kept.
+pub(crate) fn clear_container_health() {
+    let state = get_health_state();
+    let mut state_guard = state.lock().unwrap();
+    state_guard.containers.clear();
+}
+
+/// Updates tracked health from container events and raises alerts
+pub(crate) async fn handle_container_event(app_handle: &tauri::AppHandle, event: &DockerEvent) {
+    if event.event_type != "container" {
+        return;
+    }
+    let container_id = match event.actor_id.as_deref() {
+        Some(id) => id.to_string(),
+        None => return,
+    };
+    let name = event
+        .attributes
+        .get("name")
+        .cloned()
+        .unwrap_or_else(|| container_id.clone());
+
+    // Docker's own restart count is only available from inspect
+    let restart_count = if event.action == "start" {
+        inspect_health(&container_id)
+            .await
+            .ok()
+            .map(|(_, _, count)| count)
+    } else {
+        None
+    };
+
+    let config = get_monitoring_config();
+    let mut alerts = Vec::new();
+
+    let snapshot = {
+        let state = get_health_state();
+        let mut state_guard = state.lock().unwrap();
+        if event.action == "destroy" {
+            state_guard.containers.remove(&container_id);
+            return;
+        }
+
+        let entry = state_guard
+            .containers
+            .entry(container_id.clone())
+            .or_default();
+        entry.container_id = container_id;
+        entry.name = name;
+
+        match event.base_action() {
+            "health_status" => {
+                let health = event
+                    .action
+                    .split_once(':')
+                    .map(|(_, status)| status.trim().to_string());
+                if health.as_deref() == Some("unhealthy") && entry.health != health {
+                    alerts.push(ContainerAlertKind::Unhealthy);
+                }
+                entry.health = health;
+            }
+            "kill" => entry.last_kill = Some(Instant::now()),
+            "die" => {
+                let exit_code = event
+                    .attributes
+                    .get("exitCode")
+                    .and_then(|code| code.parse::<i64>().ok());
+                entry.last_exit_code = exit_code;
+
+                let deliberate = entry
+                    .last_kill
+                    .is_some_and(|t| t.elapsed() < KILL_GRACE_PERIOD);
+                if let Some(exit_code) = exit_code.filter(|code| *code != 0) {
+                    if !deliberate {
+                        alerts.push(ContainerAlertKind::NonZeroExit { exit_code });
+                    }
+                }
+                entry.last_kill = None;
+            }
+            "start" => {
+                if let Some(count) = restart_count {
+                    entry.restart_count = count;
+                }
+                // Health checks start over after a restart
+                entry.health = None;
+
+                let now = Instant::now();
+                entry.recent_starts.push_back(now);
+                while entry
+                    .recent_starts
+                    .front()
+                    .is_some_and(|t| now.duration_since(*t) > config.restart_alert_window)
+                {
+                    entry.recent_starts.pop_front();
+                }
+
+                // The first start in the window is not a restart
+                let restarts = entry.recent_starts.len().saturating_sub(1);
+                let recently_alerted = entry
+                    .restart_loop_alerted_at
+                    .is_some_and(|t| now.duration_since(t) < config.restart_alert_window);
+                if restarts > config.restart_alert_threshold as usize && !recently_alerted {
+                    entry.restart_loop_alerted_at = Some(now);
+                    alerts.push(ContainerAlertKind::RestartLoop {
+                        restarts,
+                        window_secs: config.restart_alert_window.as_secs(),
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        entry.clone()
+    };
+
+    for kind in alerts {
+        raise_alert(app_handle, &snapshot, kind);
+    }
+}
+
+/// Tauri command to get recent container alerts, newest first
+#[tauri::command]
+pub async fn get_container_alerts(
+    container: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<ContainerAlert>, DockerError> {
+    let state = get_health_state();
+    let state_guard = state.lock().unwrap();
+
+    Ok(state_guard
+        .alerts
+        .iter()
+        .rev()
+        .filter(|alert| {
+            container
+                .as_ref()
+                .is_none_or(|c| &alert.container_id == c || &alert.container_name == c)
+        })
+        .take(limit.unwrap_or(MAX_ALERTS))
+        .cloned()
+        .collect())
+}
+
+/// Tauri command to get the tracked health of every known container
+#[tauri::command]
+pub async fn get_container_health() -> Result<Vec<ContainerHealth>, DockerError> {
+    let state = get_health_state();
+    let state_guard = state.lock().unwrap();
+    Ok(state_guard.containers.values().cloned().collect())
+}
@@ -0,0 +1,220 @@
+use bollard::models::ImageDeleteResponseItem;
+use bollard::query_parameters::{
+    CreateImageOptions, ListImagesOptions, PruneImagesOptions, RemoveImageOptions,
+};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::Emitter;
+
+use super::docker::{get_connection_timeout, get_docker_client, DockerError};
+
+/// A local image as returned by `list_docker_images`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageInfo {
+    pub id: String,
+    pub repo_tags: Vec<String>,
+    pub repo_digests: Vec<String>,
+    pub size: i64,
+    pub shared_size: i64,
+    pub created: Option<String>,
+    pub containers: i64,
+    pub labels: HashMap<String, String>,
+    /// Untagged image that is no longer referenced by any tag
+    pub dangling: bool,
+}
+
+/// Layer progress emitted as `docker-image-pull-progress` while pulling
+#[derive(Debug, Clone, Serialize)]
+pub struct ImagePullProgress {
+    pub image: String,
+    pub layer_id: Option<String>,
+    pub status: Option<String>,
+    pub current: Option<i64>,
+    pub total: Option<i64>,
+}
+
+/// Tags and image ids affected by a remove or prune
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImageRemovalResult {
+    pub untagged: Vec<String>,
+    pub deleted: Vec<String>,
+    pub space_reclaimed: u64,
+}
+
+impl From<bollard::models::ImageSummary> for ImageInfo {
+    fn from(image: bollard::models::ImageSummary) -> Self {
+        // Older daemons report "<none>:<none>" instead of an empty tag list
+        let repo_tags: Vec<String> = image
+            .repo_tags
+            .into_iter()
+            .filter(|tag| tag != "<none>:<none>")
+            .collect();
+
+        Self {
+            id: image.id,
+            dangling: repo_tags.is_empty(),
+            repo_tags,
+            repo_digests: image
+                .repo_digests
+                .into_iter()
+                .filter(|digest| digest != "<none>@<none>")
+                .collect(),
+            size: image.size,
+            shared_size: image.shared_size,
+            created: chrono::DateTime::from_timestamp(image.created, 0).map(|dt| dt.to_rfc3339()),
+            containers: image.containers,
+            labels: image.labels,
+        }
+    }
+}
+
+fn collect_removals(items: Vec<ImageDeleteResponseItem>, result: &mut ImageRemovalResult) {
+    for item in items {
+        if let Some(untagged) = item.untagged {
+            result.untagged.push(untagged);
+        }
+        if let Some(deleted) = item.deleted {
+            result.deleted.push(deleted);
+        }
+    }
+}
+
+/// Tauri command to list local images
+#[tauri::command]
+pub async fn list_docker_images(
+    all: Option<bool>,
+    dangling_only: Option<bool>,
+) -> Result<Vec<ImageInfo>, DockerError> {
+    let docker = get_docker_client()?;
+
+    let filters = if dangling_only.unwrap_or(false) {
+        Some(HashMap::from([(
+            "dangling".to_string(),
+            vec!["true".to_string()],
+        )]))
+    } else {
+        None
+    };
+
+    let images = tokio::time::timeout(
+        get_connection_timeout(),
+        docker.list_images(Some(ListImagesOptions {
+            all: all.unwrap_or(false),
+            filters,
+            digests: true,
+            ..Default::default()
+        })),
+    )
+    .await
+    .map_err(|_| DockerError::Timeout)??;
+
+    Ok(images.into_iter().map(ImageInfo::from).collect())
+}
+
+/// Pulls an image, emitting `docker-image-pull-progress` for every layer update.
+/// Resolves once the pull has finished.
+#[tauri::command]
+pub async fn pull_docker_image(
+    app_handle: tauri::AppHandle,
+    image: String,
+    tag: Option<String>,
+) -> Result<(), DockerError> {
+    let docker = get_docker_client()?;
+
+    // Accept both "nginx" + tag and a full "nginx:1.27" reference
+    let tag = tag.unwrap_or_else(|| "latest".to_string());
+    let reference = if image.contains('@') || image.rsplit('/').next().unwrap_or("").contains(':') {
+        image.clone()
+    } else {
+        format!("{}:{}", image, tag)
+    };
+
+    let mut stream = docker.create_image(
+        Some(CreateImageOptions {
+            from_image: Some(reference.clone()),
+            ..Default::default()
+        }),
+        None,
+        None,
+    );
+
+    while let Some(result) = stream.next().await {
+        let info = result?;
+        if let Some(error) = info.error_detail.and_then(|d| d.message).or(info.error) {
+            return Err(DockerError::RequestFailed(error));
+        }
+
+        let detail = info.progress_detail;
+        if let Err(error) = app_handle.emit(
+            "docker-image-pull-progress",
+            ImagePullProgress {
+                image: reference.clone(),
+                layer_id: info.id,
+                status: info.status,
+                current: detail.as_ref().and_then(|d| d.current),
+                total: detail.as_ref().and_then(|d| d.total),
+            },
+        ) {
+            tracing::warn!(%error, "Could not emit docker-image-pull-progress");
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes an image by id or tag
+#[tauri::command]
+pub async fn remove_docker_image(
+    image: String,
+    force: Option<bool>,
+    no_prune: Option<bool>,
+) -> Result<ImageRemovalResult, DockerError> {
+    let docker = get_docker_client()?;
+
+    let items = docker
+        .remove_image(
+            &image,
+            Some(RemoveImageOptions {
+                force: force.unwrap_or(false),
+                noprune: no_prune.unwrap_or(false),
+            }),
+            None,
+        )
+        .await?;
+
+    let mut result = ImageRemovalResult::default();
+    collect_removals(items, &mut result);
+    Ok(result)
+}
+
+/// Prunes dangling images, or every image not used by a container when `all_unused` is set
+#[tauri::command]
+pub async fn prune_docker_images(
+    all_unused: Option<bool>,
+) -> Result<ImageRemovalResult, DockerError> {
+    let docker = get_docker_client()?;
+
+    // The daemon treats dangling=false as "all unused images"
+    let dangling = if all_unused.unwrap_or(false) {
+        "false"
+    } else {
+        "true"
+    };
+
+    let response = docker
+        .prune_images(Some(PruneImagesOptions {
+            filters: Some(HashMap::from([(
+                "dangling".to_string(),
+                vec![dangling.to_string()],
+            )])),
+        }))
+        .await?;
+
+    let mut result = ImageRemovalResult {
+        space_reclaimed: response.space_reclaimed.unwrap_or(0).max(0) as u64,
+        ..Default::default()
+    };
+    collect_removals(response.images_deleted.unwrap_or_default(), &mut result);
+    Ok(result)
+}
@@ -0,0 +1,179 @@
+use bollard::container::LogOutput;
+use bollard::query_parameters::LogsOptions;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::async_runtime::JoinHandle;
+use tauri::Emitter;
+
+use super::docker::{get_docker_client, DockerError};
+
+/// Options accepted by `stream_container_logs`
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogStreamOptions {
+    /// Number of lines to return from the end of the log (all lines if unset)
+    pub tail: Option<u64>,
+    /// Only return logs after this RFC 3339 timestamp
+    pub since: Option<String>,
+    /// Only return logs before this RFC 3339 timestamp
+    pub until: Option<String>,
+    /// Prefix every line with Docker's RFC 3339 timestamp
+    #[serde(default)]
+    pub timestamps: bool,
+    /// Keep the stream open for new output (defaults to true)
+    pub follow: Option<bool>,
+}
+
+impl Default for LogStreamOptions {
+    fn default() -> Self {
+        Self {
+            tail: None,
+            since: None,
+            until: None,
+            timestamps: false,
+            follow: Some(true),
+        }
+    }
+}
+
+/// A chunk of container output emitted as `docker-log-chunk`
+#[derive(Debug, Clone, Serialize)]
+pub struct LogChunk {
+    pub subscription_id: String,
+    pub stream: String,
+    pub message: String,
+}
+
+/// Emitted as `docker-log-end` when a log stream finishes or fails
+#[derive(Debug, Clone, Serialize)]
+pub struct LogStreamEnd {
+    pub subscription_id: String,
+    pub error: Option<String>,
+}
+
+// Running log streams keyed by subscription id
+type LogStreams = Arc<Mutex<HashMap<String, JoinHandle<()>>>>;
+
+static LOG_STREAMS: std::sync::OnceLock<LogStreams> = std::sync::OnceLock::new();
+static NEXT_SUBSCRIPTION_ID: AtomicU64 = AtomicU64::new(1);
+
+fn get_log_streams() -> LogStreams {
+    LOG_STREAMS
+        .get_or_init(|| Arc::new(Mutex::new(HashMap::new())))
+        .clone()
+}
+
+/// Parses an optional RFC 3339 timestamp into the unix seconds the Docker API expects
+fn parse_timestamp(value: &Option<String>, field: &str) -> Result<i32, DockerError> {
+    match value {
+        Some(value) => chrono::DateTime::parse_from_rfc3339(value)
+            .map(|dt| dt.timestamp() as i32)
+            .map_err(|e| DockerError::InvalidInput(format!("{} '{}': {}", field, value, e))),
+        None => Ok(0),
+    }
+}
+
+fn to_chunk(subscription_id: &str, output: LogOutput) -> LogChunk {
+    let stream = match &output {
+        LogOutput::StdErr { .. } => "stderr",
+        LogOutput::StdOut { .. } => "stdout",
+        LogOutput::StdIn { .. } => "stdin",
+        LogOutput::Console { .. } => "console",
+    };
+
+    LogChunk {
+        subscription_id: subscription_id.to_string(),
+        stream: stream.to_string(),
+        message: output.to_string(),
+    }
+}
+
+/// Starts following a container's stdout/stderr and returns the subscription id
+/// used to tag `docker-log-chunk` events
+#[tauri::command]
+pub async fn stream_container_logs(
+    app_handle: tauri::AppHandle,
+    container: String,
+    options: Option<LogStreamOptions>,
+) -> Result<String, DockerError> {
+    let docker = get_docker_client()?;
+    let options = options.unwrap_or_default();
+
+    let logs_options = LogsOptions {
+        follow: options.follow.unwrap_or(true),
+        stdout: true,
+        stderr: true,
+        since: parse_timestamp(&options.since, "since")?,
+        until: parse_timestamp(&options.until, "until")?,
+        timestamps: options.timestamps,
+        tail: options
+            .tail
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "all".to_string()),
+    };
+
+    let subscription_id = format!(
+        "logs-{}",
+        NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::Relaxed)
+    );
+
+    // Hold the registry lock until the handle is stored so a stream that ends
+    // immediately cannot try to deregister itself before it was registered
+    let streams = get_log_streams();
+    let mut streams_guard = streams.lock().unwrap();
+
+    let id = subscription_id.clone();
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut stream = docker.logs(&container, Some(logs_options));
+        let mut error = None;
+
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(output) => {
+                    if let Err(error) = app_handle.emit("docker-log-chunk", to_chunk(&id, output)) {
+                        tracing::warn!(%error, "Could not emit docker-log-chunk");
+                    }
+                }
+                Err(e) => {
+                    error = Some(DockerError::from(e).to_string());
+                    break;
+                }
+            }
+        }
+
+        get_log_streams().lock().unwrap().remove(&id);
+        if let Err(error) = app_handle.emit(
+            "docker-log-end",
+            LogStreamEnd {
+                subscription_id: id,
+                error,
+            },
+        ) {
+            tracing::warn!(%error, "Could not emit docker-log-end");
+        }
+    });
+
+    streams_guard.insert(subscription_id.clone(), handle);
+    drop(streams_guard);
+
+    Ok(subscription_id)
+}
+
+/// Stops a log stream started by `stream_container_logs`
+#[tauri::command]
+pub async fn cancel_log_stream(subscription_id: String) -> Result<(), DockerError> {
+    let handle = get_log_streams().lock().unwrap().remove(&subscription_id);
+
+    match handle {
+        Some(handle) => {
+            handle.abort();
+            Ok(())
+        }
+        None => Err(DockerError::NotFound(format!(
+            "log stream {}",
+            subscription_id
+        ))),
+    }
+}
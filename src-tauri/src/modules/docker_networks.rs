@@ -0,0 +1,166 @@
+use bollard::models::{Ipam, IpamConfig, NetworkCreateRequest};
+use bollard::query_parameters::{InspectNetworkOptions, ListNetworksOptions, PruneNetworksOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::docker::{get_connection_timeout, get_docker_client, DockerError};
+
+/// IP address management settings for one subnet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkSubnet {
+    pub subnet: Option<String>,
+    pub gateway: Option<String>,
+    pub ip_range: Option<String>,
+}
+
+/// A container attached to a network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkEndpoint {
+    pub container_id: String,
+    pub name: Option<String>,
+    pub ipv4_address: Option<String>,
+    pub ipv6_address: Option<String>,
+    pub mac_address: Option<String>,
+}
+
+/// A Docker network and the containers attached to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkInfo {
+    pub id: String,
+    pub name: String,
+    pub driver: Option<String>,
+    pub scope: Option<String>,
+    pub internal: bool,
+    pub attachable: bool,
+    pub subnets: Vec<NetworkSubnet>,
+    pub containers: Vec<NetworkEndpoint>,
+    pub labels: HashMap<String, String>,
+    pub created: Option<String>,
+}
+
+impl From<bollard::models::Network> for NetworkInfo {
+    fn from(network: bollard::models::Network) -> Self {
+        let subnets = network
+            .ipam
+            .and_then(|ipam| ipam.config)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|config| NetworkSubnet {
+                subnet: config.subnet,
+                gateway: config.gateway,
+                ip_range: config.ip_range,
+            })
+            .collect();
+
+        let containers = network
+            .containers
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(container_id, endpoint)| NetworkEndpoint {
+                container_id,
+                name: endpoint.name,
+                ipv4_address: endpoint.ipv4_address.filter(|a| !a.is_empty()),
+                ipv6_address: endpoint.ipv6_address.filter(|a| !a.is_empty()),
+                mac_address: endpoint.mac_address.filter(|a| !a.is_empty()),
+            })
+            .collect();
+
+        Self {
+            id: network.id.unwrap_or_default(),
+            name: network.name.unwrap_or_default(),
+            driver: network.driver,
+            scope: network.scope,
+            internal: network.internal.unwrap_or(false),
+            attachable: network.attachable.unwrap_or(false),
+            subnets,
+            containers,
+            labels: network.labels.unwrap_or_default(),
+            created: network.created,
+        }
+    }
+}
+
+/// Tauri command to list networks with their subnets and attached containers
+#[tauri::command]
+pub async fn list_docker_networks() -> Result<Vec<NetworkInfo>, DockerError> {
+    let docker = get_docker_client()?;
+
+    let networks = tokio::time::timeout(
+        get_connection_timeout(),
+        docker.list_networks(None::<ListNetworksOptions>),
+    )
+    .await
+    .map_err(|_| DockerError::Timeout)??;
+
+    // The list endpoint leaves `Containers` empty, so inspect each network for it
+    let mut result = Vec::with_capacity(networks.len());
+    for network in networks {
+        let inspected = match network.id.as_deref() {
+            Some(id) => docker
+                .inspect_network(id, None::<InspectNetworkOptions>)
+                .await
+                .unwrap_or(network),
+            None => network,
+        };
+        result.push(NetworkInfo::from(inspected));
+    }
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn create_docker_network(
+    name: String,
+    driver: Option<String>,
+    subnet: Option<String>,
+    gateway: Option<String>,
+    internal: Option<bool>,
+    labels: Option<HashMap<String, String>>,
+) -> Result<String, DockerError> {
+    // A gateway only means something inside a subnet
+    if gateway.is_some() && subnet.is_none() {
+        return Err(DockerError::InvalidInput(
+            "gateway requires a subnet".to_string(),
+        ));
+    }
+    let docker = get_docker_client()?;
+
+    let ipam = subnet.map(|subnet| Ipam {
+        config: Some(vec![IpamConfig {
+            subnet: Some(subnet),
+            gateway,
+            ..Default::default()
+        }]),
+        ..Default::default()
+    });
+
+    let response = docker
+        .create_network(NetworkCreateRequest {
+            name,
+            driver,
+            internal,
+            ipam,
+            labels,
+            ..Default::default()
+        })
+        .await?;
+
+    Ok(response.id)
+}
+
+#[tauri::command]
+pub async fn remove_docker_network(network: String) -> Result<(), DockerError> {
+    let docker = get_docker_client()?;
+    docker.remove_network(&network).await?;
+    Ok(())
+}
+
+/// Removes networks not used by any container and returns their names
+#[tauri::command]
+pub async fn prune_docker_networks() -> Result<Vec<String>, DockerError> {
+    let docker = get_docker_client()?;
+
+    let response = docker.prune_networks(None::<PruneNetworksOptions>).await?;
+
+    Ok(response.networks_deleted.unwrap_or_default())
+}
@@ -0,0 +1,486 @@
+use bollard::models::ContainerStatsResponse;
+use bollard::query_parameters::{ListContainersOptions, StatsOptions};
+use futures_util::StreamExt;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::async_runtime::JoinHandle;
+use tauri::Emitter;
+
+use super::docker::{
+    get_connection_timeout, get_docker_client, get_stats_interval, DockerError, DockerEvent,
+};
+
+/// Resource usage for one container, emitted as `docker-container-stats`
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerStats {
+    pub id: String,
+    pub name: String,
+    pub timestamp: String,
+    pub cpu_percent: f64,
+    pub online_cpus: u32,
+    pub memory_usage_bytes: u64,
+    pub memory_limit_bytes: u64,
+    pub memory_percent: f64,
+    pub network_rx_bytes: Option<u64>,
+    pub network_tx_bytes: Option<u64>,
+    /// Bytes received/sent since the previous emitted sample
+    pub network_rx_delta: Option<u64>,
+    pub network_tx_delta: Option<u64>,
+    pub block_read_bytes: u64,
+    pub block_write_bytes: u64,
+    /// Bytes read/written since the previous emitted sample
+    pub block_read_delta: u64,
+    pub block_write_delta: u64,
+    pub pids: Option<u64>,
+}
+
+// Per-container stats subscriptions and the latest sample from each
+#[derive(Default)]
+struct StatsState {
+    // The generation lets a finished stream tell whether its entry was already
+    // replaced by a newer subscription for the same container
+    monitors: HashMap<String, (u64, JoinHandle<()>)>,
+    latest: HashMap<String, ContainerStats>,
+    next_generation: u64,
+}
+
+static STATS_STATE: std::sync::OnceLock<Arc<Mutex<StatsState>>> = std::sync::OnceLock::new();
+
+fn get_stats_state() -> Arc<Mutex<StatsState>> {
+    STATS_STATE
+        .get_or_init(|| Arc::new(Mutex::new(StatsState::default())))
+        .clone()
+}
+
+/// CPU percentage as computed by `docker stats`: the container's share of the
+/// host CPU time elapsed between two samples, scaled by the number of CPUs
+fn cpu_percent(stats: &ContainerStatsResponse) -> (f64, u32) {
+    let (cpu, precpu) = match (&stats.cpu_stats, &stats.precpu_stats) {
+        (Some(cpu), Some(precpu)) => (cpu, precpu),
+        _ => return (0.0, 0),
+    };
+
+    let total = |s: &bollard::models::ContainerCpuStats| {
+        s.cpu_usage
+            .as_ref()
+            .and_then(|u| u.total_usage)
+            .unwrap_or(0)
+    };
+    let online_cpus = cpu.online_cpus.unwrap_or_else(|| {
+        cpu.cpu_usage
+            .as_ref()
+            .and_then(|u| u.percpu_usage.as_ref())
+            .map(|p| p.len() as u32)
+            .unwrap_or(1)
+    });
+
+    let cpu_delta = total(cpu).saturating_sub(total(precpu)) as f64;
+    let system_delta = cpu
+        .system_cpu_usage
+        .unwrap_or(0)
+        .saturating_sub(precpu.system_cpu_usage.unwrap_or(0)) as f64;
+
+    if cpu_delta > 0.0 && system_delta > 0.0 {
+        (
+            cpu_delta / system_delta * online_cpus as f64 * 100.0,
+            online_cpus,
+        )
+    } else {
+        (0.0, online_cpus)
+    }
+}
+
+/// Memory usage excluding the page cache, matching `docker stats`
+fn memory_usage(stats: &ContainerStatsResponse) -> (u64, u64) {
+    let memory = match &stats.memory_stats {
+        Some(memory) => memory,
+        None => return (0, 0),
+    };
+
+    let usage = memory.usage.unwrap_or(0);
+    // cgroup v2 reports "inactive_file", cgroup v1 "total_inactive_file"
+    let cache = memory
+        .stats
+        .as_ref()
+        .and_then(|s| {
+            s.get("inactive_file")
+                .or_else(|| s.get("total_inactive_file"))
+        })
+        .copied()
+        .unwrap_or(0);
+
+    (usage.saturating_sub(cache), memory.limit.unwrap_or(0))
+}
+
+fn block_io(stats: &ContainerStatsResponse) -> (u64, u64) {
+    let entries = stats
+        .blkio_stats
+        .as_ref()
+        .and_then(|b| b.io_service_bytes_recursive.as_ref());
+
+    let mut read = 0;
+    let mut write = 0;
+    for entry in entries.into_iter().flatten() {
+        let value = entry.value.unwrap_or(0);
+        match entry.op.as_deref().map(str::to_ascii_lowercase).as_deref() {
+            Some("read") => read += value,
+            Some("write") => write += value,
+            _ => {}
+        }
+    }
+    (read, write)
+}
+
+/// Bytes received and sent, summed over every network interface. `None` when
+/// the container has no networking.
+fn network_io(stats: &ContainerStatsResponse) -> (Option<u64>, Option<u64>) {
+    let Some(networks) = stats.networks.as_ref().filter(|n| !n.is_empty()) else {
+        return (None, None);
+    };
+    let rx = networks.values().map(|n| n.rx_bytes.unwrap_or(0)).sum();
+    let tx = networks.values().map(|n| n.tx_bytes.unwrap_or(0)).sum();
+    (Some(rx), Some(tx))
+}
+
+fn to_container_stats(
+    id: &str,
+    stats: &ContainerStatsResponse,
+    previous: Option<&ContainerStats>,
+) -> ContainerStats {
+    let (cpu_percent, online_cpus) = cpu_percent(stats);
+    let (memory_usage_bytes, memory_limit_bytes) = memory_usage(stats);
+    let memory_percent = if memory_limit_bytes > 0 {
+        memory_usage_bytes as f64 / memory_limit_bytes as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    let (network_rx_bytes, network_tx_bytes) = network_io(stats);
+    let (block_read_bytes, block_write_bytes) = block_io(stats);
+
+    let delta =
+        |current: u64, previous: Option<u64>| current.saturating_sub(previous.unwrap_or(current));
+
+    ContainerStats {
+        id: id.to_string(),
+        name: stats
+            .name
+            .as_deref()
+            .unwrap_or_default()
+            .trim_start_matches('/')
+            .to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        cpu_percent,
+        online_cpus,
+        memory_usage_bytes,
+        memory_limit_bytes,
+        memory_percent,
+        network_rx_bytes,
+        network_tx_bytes,
+        network_rx_delta: network_rx_bytes
+            .map(|rx| delta(rx, previous.and_then(|p| p.network_rx_bytes))),
+        network_tx_delta: network_tx_bytes
+            .map(|tx| delta(tx, previous.and_then(|p| p.network_tx_bytes))),
+        block_read_bytes,
+        block_write_bytes,
+        block_read_delta: delta(block_read_bytes, previous.map(|p| p.block_read_bytes)),
+        block_write_delta: delta(block_write_bytes, previous.map(|p| p.block_write_bytes)),
+        pids: stats.pids_stats.as_ref().and_then(|p| p.current),
+    }
+}
+
+/// Subscribes to a container's stats stream unless it is already being watched
+pub(crate) fn attach_container_stats(app_handle: &tauri::AppHandle, container_id: &str) {
+    let docker = match get_docker_client() {
+        Ok(docker) => docker,
+        Err(_) => return,
+    };
+
+    let state = get_stats_state();
+    let mut state_guard = state.lock().unwrap();
+    if state_guard.monitors.contains_key(container_id) {
+        return;
+    }
+    state_guard.next_generation += 1;
+    let generation = state_guard.next_generation;
+
+    let id = container_id.to_string();
+    let app_handle = app_handle.clone();
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut stream = docker.stats(
+            &id,
+            Some(StatsOptions {
+                stream: true,
+                one_shot: false,
+            }),
+        );
+        let mut last_emit: Option<Instant> = None;
+
+        while let Some(result) = stream.next().await {
+            let sample = match result {
+                Ok(sample) => sample,
+                Err(error) => {
+                    tracing::warn!(container = %id, %error, "Stats stream failed");
+                    break;
+                }
+            };
+            // Docker streams roughly one sample per second; emit at the configured rate
+            if last_emit.is_some_and(|t| t.elapsed() < get_stats_interval()) {
+                continue;
+            }
+            last_emit = Some(Instant::now());
+
+            let stats = {
+                let state = get_stats_state();
+                let mut state_guard = state.lock().unwrap();
+                let stats = to_container_stats(&id, &sample, state_guard.latest.get(&id));
+                state_guard.latest.insert(id.clone(), stats.clone());
+                stats
+            };
+            if let Err(error) = app_handle.emit("docker-container-stats", stats) {
+                tracing::warn!(%error, "Could not emit docker-container-stats");
+            }
+        }
+
+        // The stream ends when the container stops, the connection drops or
+        // the daemon reports an error
+        let state = get_stats_state();
+        let mut state_guard = state.lock().unwrap();
+        if state_guard.monitors.get(&id).map(|(g, _)| *g) == Some(generation) {
+            state_guard.monitors.remove(&id);
+            state_guard.latest.remove(&id);
+        }
+    });
+
+    state_guard
+        .monitors
+        .insert(container_id.to_string(), (generation, handle));
+}
+
+/// Stops watching a container's stats and forgets its latest sample
+pub(crate) fn detach_container_stats(container_id: &str) {
+    let state = get_stats_state();
+    let mut state_guard = state.lock().unwrap();
+    if let Some((_, handle)) = state_guard.monitors.remove(container_id) {
+        handle.abort();
+    }
+    state_guard.latest.remove(container_id);
+}
+
+/// Stops every stats subscription, e.g. after the Docker connection is lost
+pub(crate) fn detach_all_container_stats() {
+    let state = get_stats_state();
+    let mut state_guard = state.lock().unwrap();
+    for (_, (_, handle)) in state_guard.monitors.drain() {
+        handle.abort();
+    }
+    state_guard.latest.clear();
+}
+
+/// Attaches to every currently running container
+pub(crate) async fn attach_running_containers(
+    app_handle: &tauri::AppHandle,
+) -> Result<(), DockerError> {
+    let docker = get_docker_client()?;
+    let containers = tokio::time::timeout(
+        get_connection_timeout(),
+        docker.list_containers(None::<ListContainersOptions>),
+    )
+    .await
+    .map_err(|_| DockerError::Timeout)??;
+
+    for id in containers.into_iter().filter_map(|c| c.id) {
+        attach_container_stats(app_handle, &id);
+    }
+    Ok(())
+}
+
+/// Attaches or detaches stats subscriptions as containers start and die
+pub(crate) fn handle_container_event(app_handle: &tauri::AppHandle, event: &DockerEvent) {
+    if event.event_type != "container" {
+        return;
+    }
+    let id = match event.actor_id.as_deref() {
+        Some(id) => id,
+        None => return,
+    };
+
+    match event.action.as_str() {
+        "start" => attach_container_stats(app_handle, id),
+        "die" | "destroy" => detach_container_stats(id),
+        _ => {}
+    }
+}
+
+/// Latest stats sample for every watched container
+pub(crate) fn latest_container_stats() -> Vec<ContainerStats> {
+    let state = get_stats_state();
+    let state_guard = state.lock().unwrap();
+    state_guard.latest.values().cloned().collect()
+}
+
+/// Tauri command to get the latest stats sample for every watched container
+#[tauri::command]
+pub async fn get_container_stats() -> Result<Vec<ContainerStats>, String> {
+    Ok(latest_container_stats())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::{
+        ContainerBlkioStatEntry, ContainerBlkioStats, ContainerCpuStats, ContainerCpuUsage,
+        ContainerMemoryStats, ContainerNetworkStats,
+    };
+
+    fn cpu(total: u64, system: u64, online: Option<u32>) -> ContainerCpuStats {
+        ContainerCpuStats {
+            cpu_usage: Some(ContainerCpuUsage {
+                total_usage: Some(total),
+                percpu_usage: Some(vec![0; 2]),
+                ..Default::default()
+            }),
+            system_cpu_usage: Some(system),
+            online_cpus: online,
+            ..Default::default()
+        }
+    }
+
+    fn with_cpu(current: ContainerCpuStats, previous: ContainerCpuStats) -> ContainerStatsResponse {
+        ContainerStatsResponse {
+            cpu_stats: Some(current),
+            precpu_stats: Some(previous),
+            ..Default::default()
+        }
+    }
+
+    fn with_memory(usage: u64, limit: u64, cache: Option<(&str, u64)>) -> ContainerStatsResponse {
+        ContainerStatsResponse {
+            memory_stats: Some(ContainerMemoryStats {
+                usage: Some(usage),
+                limit: Some(limit),
+                stats: cache.map(|(key, value)| HashMap::from([(key.to_string(), value)])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn with_io(networks: &[(&str, u64, u64)], block: &[(&str, u64)]) -> ContainerStatsResponse {
+        ContainerStatsResponse {
+            name: Some("/web".to_string()),
+            networks: Some(
+                networks
+                    .iter()
+                    .map(|(name, rx, tx)| {
+                        let stats = ContainerNetworkStats {
+                            rx_bytes: Some(*rx),
+                            tx_bytes: Some(*tx),
+                            ..Default::default()
+                        };
+                        (name.to_string(), stats)
+                    })
+                    .collect(),
+            ),
+            blkio_stats: Some(ContainerBlkioStats {
+                io_service_bytes_recursive: Some(
+                    block
+                        .iter()
+                        .map(|(op, value)| ContainerBlkioStatEntry {
+                            op: Some(op.to_string()),
+                            value: Some(*value),
+                            ..Default::default()
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cpu_percent_scales_the_share_of_system_time_by_cpus() {
+        let stats = with_cpu(cpu(400, 2000, Some(4)), cpu(200, 1000, Some(4)));
+        assert_eq!(cpu_percent(&stats), (80.0, 4));
+
+        // Without online_cpus the per-CPU counters are counted
+        let stats = with_cpu(cpu(400, 2000, None), cpu(200, 1000, None));
+        assert_eq!(cpu_percent(&stats), (40.0, 2));
+    }
+
+    #[test]
+    fn cpu_percent_is_zero_without_a_previous_sample_or_progress() {
+        let first = ContainerStatsResponse {
+            cpu_stats: Some(cpu(400, 2000, Some(4))),
+            ..Default::default()
+        };
+        assert_eq!(cpu_percent(&first), (0.0, 0));
+
+        let idle = with_cpu(cpu(200, 2000, Some(4)), cpu(200, 1000, Some(4)));
+        assert_eq!(cpu_percent(&idle), (0.0, 4));
+
+        // Counters going backwards, as after a container restart
+        let reset = with_cpu(cpu(100, 2000, Some(4)), cpu(200, 1000, Some(4)));
+        assert_eq!(cpu_percent(&reset), (0.0, 4));
+    }
+
+    #[test]
+    fn memory_usage_excludes_the_page_cache() {
+        let v2 = with_memory(1000, 4000, Some(("inactive_file", 300)));
+        assert_eq!(memory_usage(&v2), (700, 4000));
+        let v1 = with_memory(1000, 4000, Some(("total_inactive_file", 200)));
+        assert_eq!(memory_usage(&v1), (800, 4000));
+        let no_cache = with_memory(1000, 4000, None);
+        assert_eq!(memory_usage(&no_cache), (1000, 4000));
+        assert_eq!(memory_usage(&ContainerStatsResponse::default()), (0, 0));
+    }
+
+    #[test]
+    fn network_io_sums_interfaces() {
+        let stats = with_io(&[("eth0", 100, 10), ("eth1", 50, 5)], &[]);
+        assert_eq!(network_io(&stats), (Some(150), Some(15)));
+
+        // No networking at all, e.g. `--network none`
+        assert_eq!(network_io(&with_io(&[], &[])), (None, None));
+        assert_eq!(network_io(&ContainerStatsResponse::default()), (None, None));
+    }
+
+    #[test]
+    fn block_io_sums_reads_and_writes() {
+        let stats = with_io(
+            &[],
+            &[("Read", 100), ("write", 40), ("read", 20), ("Sync", 999)],
+        );
+        assert_eq!(block_io(&stats), (120, 40));
+        assert_eq!(block_io(&ContainerStatsResponse::default()), (0, 0));
+    }
+
+    #[test]
+    fn deltas_are_relative_to_the_previous_sample() {
+        let first = to_container_stats("id", &with_io(&[("eth0", 100, 10)], &[("read", 50)]), None);
+        assert_eq!(first.name, "web");
+        assert_eq!(first.network_rx_delta, Some(0));
+        assert_eq!(first.block_read_delta, 0);
+
+        let second = to_container_stats(
+            "id",
+            &with_io(&[("eth0", 300, 30)], &[("read", 80)]),
+            Some(&first),
+        );
+        assert_eq!(second.network_rx_delta, Some(200));
+        assert_eq!(second.network_tx_delta, Some(20));
+        assert_eq!(second.block_read_delta, 30);
+
+        // Counters that went backwards do not underflow
+        let reset = to_container_stats(
+            "id",
+            &with_io(&[("eth0", 10, 1)], &[("read", 5)]),
+            Some(&second),
+        );
+        assert_eq!(reset.network_rx_delta, Some(0));
+        assert_eq!(reset.block_read_delta, 0);
+    }
+}
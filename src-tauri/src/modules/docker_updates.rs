@@ -0,0 +1,394 @@
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+
+use super::docker_desktop::{self, DESKTOP_RELEASES_URL};
+use super::platform::Platform;
+use super::version::{is_newer, latest_on_channel, UpdateChannel};
+
+const DOCKER_HUB_URL: &str = "https://hub.docker.com";
+
+/// A version offered by an update source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailableRelease {
+    pub version: String,
+    pub release_notes: Option<String>,
+}
+
+pub(crate) type ProviderFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Vec<AvailableRelease>, String>> + Send + 'a>>;
+
+/// Somewhere the agent can learn which Docker versions exist
+pub(crate) trait UpdateProvider: Send + Sync {
+    /// Lists the releases the source currently offers, in no particular order
+    fn releases<'a>(&'a self, client: &'a reqwest::Client) -> ProviderFuture<'a>;
+}
+
+/// Tags of an image repository on Docker Hub or a registry exposing the same API
+pub(crate) struct DockerHubTags {
+    pub(crate) base_url: String,
+    pub(crate) repository: String,
+}
+
+impl UpdateProvider for DockerHubTags {
+    fn releases<'a>(&'a self, client: &'a reqwest::Client) -> ProviderFuture<'a> {
+        Box::pin(async move {
+            let url = format!(
+                "{}/v2/repositories/{}/tags/",
+                self.base_url.trim_end_matches('/'),
+                self.repository
+            );
+            // Newest first, so the latest release is on the one page fetched
+            let query = [("page_size", "100"), ("ordering", "last_updated")];
+            let json: serde_json::Value = get_json(client, &url, &query).await?;
+
+            Ok(json["results"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|tag| tag["name"].as_str())
+                .map(|name| AvailableRelease {
+                    version: name.to_string(),
+                    release_notes: None,
+                })
+                .collect())
+        })
+    }
+}
+
+/// Releases of a GitHub repository, e.g. moby/moby for the engine
+pub(crate) struct GitHubReleases {
+    pub(crate) api_url: String,
+    pub(crate) repository: String,
+}
+
+impl UpdateProvider for GitHubReleases {
+    fn releases<'a>(&'a self, client: &'a reqwest::Client) -> ProviderFuture<'a> {
+        Box::pin(async move {
+            let url = format!(
+                "{}/repos/{}/releases",
+                self.api_url.trim_end_matches('/'),
+                self.repository
+            );
+            let json: serde_json::Value = get_json(client, &url, &[("per_page", "30")]).await?;
+
+            Ok(json
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|release| !release["draft"].as_bool().unwrap_or(false))
+                .filter_map(|release| {
+                    let tag = release["tag_name"].as_str()?;
+                    Some(AvailableRelease {
+                        // moby tags engine releases as "docker-v28.0.0" or "v28.0.0"
+                        version: tag.trim_start_matches("docker-").to_string(),
+                        release_notes: release["body"].as_str().map(str::to_string),
+                    })
+                })
+                .collect())
+        })
+    }
+}
+
+/// A JSON document maintained by the team, either a single release
+/// `{"version": "...", "release_notes": "..."}`, a list of them, or
+/// `{"releases": [...]}`
+pub(crate) struct JsonFeed {
+    pub(crate) url: String,
+}
+
+impl UpdateProvider for JsonFeed {
+    fn releases<'a>(&'a self, client: &'a reqwest::Client) -> ProviderFuture<'a> {
+        Box::pin(async move {
+            let json: serde_json::Value = get_json(client, &self.url, &[]).await?;
+
+            let entries = match &json {
+                serde_json::Value::Array(entries) => entries.clone(),
+                serde_json::Value::Object(object) => match object.get("releases") {
+                    Some(serde_json::Value::Array(entries)) => entries.clone(),
+                    _ => vec![json.clone()],
+                },
+                _ => Vec::new(),
+            };
+
+            Ok(entries
+                .into_iter()
+                .filter_map(|entry| serde_json::from_value(entry).ok())
+                .collect())
+        })
+    }
+}
+
+/// Docker Desktop's own update feed for the platform the agent runs on
+pub(crate) struct DesktopAppcast {
+    pub(crate) base_url: String,
+    pub(crate) platform: Platform,
+}
+
+impl UpdateProvider for DesktopAppcast {
+    fn releases<'a>(&'a self, client: &'a reqwest::Client) -> ProviderFuture<'a> {
+        Box::pin(async move {
+            let versions =
+                docker_desktop::fetch_desktop_versions(client, &self.base_url, self.platform)
+                    .await?;
+            Ok(versions
+                .into_iter()
+                .map(|version| AvailableRelease {
+                    version,
+                    release_notes: None,
+                })
+                .collect())
+        })
+    }
+}
+
+async fn get_json(
+    client: &reqwest::Client,
+    url: &str,
+    query: &[(&str, &str)],
+) -> Result<serde_json::Value, String> {
+    let response = client
+        .get(url)
+        .query(query)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned {}", url, response.status()));
+    }
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Configured source of update information
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "provider", rename_all = "snake_case")]
+pub enum UpdateSource {
+    DockerHub {
+        base_url: String,
+        repository: String,
+    },
+    GithubReleases {
+        api_url: String,
+        repository: String,
+    },
+    JsonFeed {
+        url: String,
+    },
+    DesktopAppcast {
+        base_url: String,
+    },
+}
+
+impl UpdateSource {
+    pub fn default_engine() -> Self {
+        UpdateSource::DockerHub {
+            base_url: DOCKER_HUB_URL.to_string(),
+            repository: "library/docker".to_string(),
+        }
+    }
+
+    pub fn default_desktop() -> Self {
+        UpdateSource::DesktopAppcast {
+            base_url: DESKTOP_RELEASES_URL.to_string(),
+        }
+    }
+
+    pub(crate) fn provider(&self) -> Box<dyn UpdateProvider> {
+        match self {
+            UpdateSource::DockerHub {
+                base_url,
+                repository,
+            } => Box::new(DockerHubTags {
+                base_url: base_url.clone(),
+                repository: repository.clone(),
+            }),
+            UpdateSource::GithubReleases {
+                api_url,
+                repository,
+            } => Box::new(GitHubReleases {
+                api_url: api_url.clone(),
+                repository: repository.clone(),
+            }),
+            UpdateSource::JsonFeed { url } => Box::new(JsonFeed { url: url.clone() }),
+            UpdateSource::DesktopAppcast { base_url } => Box::new(DesktopAppcast {
+                base_url: base_url.clone(),
+                platform: Platform::current(),
+            }),
+        }
+    }
+}
+
+/// Result of comparing the running version with the newest release on a channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+    pub release_notes: Option<String>,
+}
+
+/// Asks `source` for its newest release on `channel` and compares it with
+/// `current_version`
+pub(crate) async fn check_for_update(
+    source: &UpdateSource,
+    channel: UpdateChannel,
+    current_version: &str,
+    client: &reqwest::Client,
+) -> Option<UpdateInfo> {
+    let mut releases = match source.provider().releases(client).await {
+        Ok(releases) => releases,
+        Err(error) => {
+            tracing::warn!(source = ?source, %error, "Update check failed");
+            return None;
+        }
+    };
+    let index = latest_on_channel(releases.iter().map(|r| r.version.as_str()), channel)?;
+    let latest = releases.swap_remove(index);
+
+    Some(UpdateInfo {
+        update_available: is_newer(current_version, &latest.version),
+        current_version: current_version.to_string(),
+        latest_version: latest.version,
+        release_notes: latest.release_notes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::test_support::TestServer;
+
+    async fn versions(provider: &dyn UpdateProvider) -> Vec<String> {
+        provider
+            .releases(&reqwest::Client::new())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|release| release.version)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn docker_hub_lists_tag_names() {
+        let server = TestServer::start(|_| {
+            (
+                200,
+                r#"{"results": [{"name": "27.3.1"}, {"name": "27.3.1-dind"}, {"name": "latest"}]}"#
+                    .to_string(),
+            )
+        })
+        .await;
+        let provider = DockerHubTags {
+            base_url: format!("{}/", server.url),
+            repository: "library/docker".to_string(),
+        };
+
+        assert_eq!(
+            versions(&provider).await,
+            ["27.3.1", "27.3.1-dind", "latest"]
+        );
+        assert_eq!(
+            server.requests()[0].uri,
+            "/v2/repositories/library/docker/tags/?page_size=100&ordering=last_updated"
+        );
+    }
+
+    #[tokio::test]
+    async fn github_skips_drafts_and_strips_prefix() {
+        let server = TestServer::start(|_| {
+            (
+                200,
+                r#"[
+                    {"tag_name": "docker-v28.0.1", "draft": false, "body": "Fixes"},
+                    {"tag_name": "v28.1.0-rc.1", "draft": false},
+                    {"tag_name": "v29.0.0", "draft": true}
+                ]"#
+                .to_string(),
+            )
+        })
+        .await;
+        let provider = GitHubReleases {
+            api_url: server.url.clone(),
+            repository: "moby/moby".to_string(),
+        };
+
+        let releases = provider.releases(&reqwest::Client::new()).await.unwrap();
+        let versions: Vec<&str> = releases.iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(versions, ["v28.0.1", "v28.1.0-rc.1"]);
+        assert_eq!(releases[0].release_notes.as_deref(), Some("Fixes"));
+        assert_eq!(
+            server.requests()[0].uri,
+            "/repos/moby/moby/releases?per_page=30"
+        );
+    }
+
+    #[tokio::test]
+    async fn json_feed_accepts_each_layout() {
+        for body in [
+            r#"{"version": "27.0.0", "release_notes": "Notes"}"#,
+            r#"[{"version": "27.0.0"}]"#,
+            r#"{"releases": [{"version": "27.0.0"}]}"#,
+        ] {
+            let server = TestServer::start(move |_| (200, body.to_string())).await;
+            let provider = JsonFeed {
+                url: format!("{}/feed.json", server.url),
+            };
+            assert_eq!(versions(&provider).await, ["27.0.0"], "{}", body);
+        }
+    }
+
+    #[tokio::test]
+    async fn desktop_appcast_lists_versions() {
+        let server = TestServer::start(|_| {
+            (
+                200,
+                r#"<item><enclosure sparkle:shortVersionString="4.31.0"/></item>"#.to_string(),
+            )
+        })
+        .await;
+        let provider = DesktopAppcast {
+            base_url: server.url.clone(),
+            platform: Platform::Linux,
+        };
+        assert_eq!(versions(&provider).await, ["4.31.0"]);
+    }
+
+    #[tokio::test]
+    async fn server_errors_fail_the_check() {
+        let server = TestServer::start(|_| (503, String::new())).await;
+        let provider = JsonFeed {
+            url: server.url.clone(),
+        };
+        assert!(provider.releases(&reqwest::Client::new()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn check_picks_latest_on_channel() {
+        let server = TestServer::start(|_| {
+            (
+                200,
+                r#"{"results": [{"name": "27.3.1"}, {"name": "27.4.0-rc.1"}, {"name": "27.3.2-dind"}]}"#
+                    .to_string(),
+            )
+        })
+        .await;
+        let source = UpdateSource::DockerHub {
+            base_url: server.url.clone(),
+            repository: "library/docker".to_string(),
+        };
+        let client = reqwest::Client::new();
+
+        let stable = check_for_update(&source, UpdateChannel::Stable, "27.3.1", &client)
+            .await
+            .unwrap();
+        assert_eq!(stable.latest_version, "27.3.1");
+        assert!(!stable.update_available);
+
+        let test = check_for_update(&source, UpdateChannel::Test, "27.3.1", &client)
+            .await
+            .unwrap();
+        assert_eq!(test.latest_version, "27.4.0-rc.1");
+        assert!(test.update_available);
+    }
+}
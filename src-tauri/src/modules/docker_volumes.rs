@@ -0,0 +1,157 @@
+use bollard::models::VolumeCreateOptions;
+use bollard::query_parameters::{DataUsageOptions, ListVolumesOptions, PruneVolumesOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::docker::{get_connection_timeout, get_docker_client, DockerError};
+
+/// A Docker volume with its disk usage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeInfo {
+    pub name: String,
+    pub driver: String,
+    pub mountpoint: String,
+    pub created: Option<String>,
+    pub scope: Option<String>,
+    pub labels: HashMap<String, String>,
+    /// Number of containers referencing the volume, when the daemon reports it
+    pub ref_count: Option<i64>,
+    /// Size in bytes, when the daemon reports it
+    pub size: Option<i64>,
+}
+
+/// Result of pruning unused volumes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumePruneResult {
+    pub deleted: Vec<String>,
+    pub space_reclaimed: u64,
+}
+
+impl From<bollard::models::Volume> for VolumeInfo {
+    fn from(volume: bollard::models::Volume) -> Self {
+        // The daemon uses -1 for usage values it has not computed
+        let usage = volume.usage_data.as_ref();
+        Self {
+            name: volume.name,
+            driver: volume.driver,
+            mountpoint: volume.mountpoint,
+            created: volume.created_at,
+            scope: volume.scope.map(|s| s.to_string()),
+            labels: volume.labels,
+            ref_count: usage.map(|u| u.ref_count).filter(|n| *n >= 0),
+            size: usage.map(|u| u.size).filter(|n| *n >= 0),
+        }
+    }
+}
+
+/// Tauri command to list volumes with reference counts and sizes
+#[tauri::command]
+pub async fn list_docker_volumes() -> Result<Vec<VolumeInfo>, DockerError> {
+    let docker = get_docker_client()?;
+
+    let response = tokio::time::timeout(
+        get_connection_timeout(),
+        docker.list_volumes(None::<ListVolumesOptions>),
+    )
+    .await
+    .map_err(|_| DockerError::Timeout)??;
+
+    // The volume list omits usage data, which only `system df` computes. It
+    // can be slow on large hosts; without it, volumes are listed without sizes.
+    let df = tokio::time::timeout(
+        get_connection_timeout(),
+        docker.df(Some(DataUsageOptions {
+            _type: Some(vec!["volume".to_string()]),
+        })),
+    )
+    .await;
+    let df_volumes = match df {
+        Ok(Ok(df)) => df.volumes.unwrap_or_default(),
+        Ok(Err(error)) => {
+            tracing::warn!(%error, "Could not read volume sizes");
+            Vec::new()
+        }
+        Err(_) => {
+            tracing::warn!("Timed out reading volume sizes");
+            Vec::new()
+        }
+    };
+    let usage: HashMap<String, bollard::models::VolumeUsageData> = df_volumes
+        .into_iter()
+        .filter_map(|v| v.usage_data.map(|u| (v.name, u)))
+        .collect();
+
+    let volumes = response
+        .volumes
+        .unwrap_or_default()
+        .into_iter()
+        .map(|mut volume| {
+            if volume.usage_data.is_none() {
+                volume.usage_data = usage.get(&volume.name).cloned();
+            }
+            VolumeInfo::from(volume)
+        })
+        .collect();
+
+    Ok(volumes)
+}
+
+#[tauri::command]
+pub async fn create_docker_volume(
+    name: String,
+    driver: Option<String>,
+    labels: Option<HashMap<String, String>>,
+) -> Result<VolumeInfo, DockerError> {
+    let docker = get_docker_client()?;
+
+    let volume = docker
+        .create_volume(VolumeCreateOptions {
+            name: Some(name),
+            driver,
+            labels,
+            ..Default::default()
+        })
+        .await?;
+
+    Ok(VolumeInfo::from(volume))
+}
+
+#[tauri::command]
+pub async fn remove_docker_volume(name: String, force: Option<bool>) -> Result<(), DockerError> {
+    let docker = get_docker_client()?;
+
+    // bollard 0.19 still only accepts its deprecated options type here
+    #[allow(deprecated)]
+    docker
+        .remove_volume(
+            &name,
+            Some(bollard::volume::RemoveVolumeOptions {
+                force: force.unwrap_or(false),
+            }),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Removes volumes not referenced by any container. By default the daemon only
+/// prunes anonymous volumes; `include_named` removes unused named volumes too.
+#[tauri::command]
+pub async fn prune_docker_volumes(
+    include_named: Option<bool>,
+) -> Result<VolumePruneResult, DockerError> {
+    let docker = get_docker_client()?;
+
+    let filters = include_named
+        .unwrap_or(false)
+        .then(|| HashMap::from([("all".to_string(), vec!["true".to_string()])]));
+
+    let response = docker
+        .prune_volumes(Some(PruneVolumesOptions { filters }))
+        .await?;
+
+    Ok(VolumePruneResult {
+        deleted: response.volumes_deleted.unwrap_or_default(),
+        space_reclaimed: response.space_reclaimed.unwrap_or(0).max(0) as u64,
+    })
+}
@@ -0,0 +1,316 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tauri::Manager;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::time::ChronoUtc;
+use tracing_subscriber::fmt::writer::{MakeWriter, OptionalWriter};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+/// Log files are named `agent.<date>.log`
+const LOG_FILE_PREFIX: &str = "agent";
+const LOG_FILE_SUFFIX: &str = "log";
+/// Number of daily log files kept, including the current one
+const MAX_LOG_FILES: usize = 7;
+/// Number of entries kept in memory for `get_recent_logs`
+const MAX_RECENT_LOGS: usize = 1000;
+/// Prefix of this crate's targets; dependencies are only logged from `warn` up
+const AGENT_TARGET: &str = "desktop_agent_lib";
+
+/// Verbosity of the agent's log, from least to most verbose
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    fn from_level(level: &Level) -> Self {
+        match *level {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warn,
+            Level::INFO => LogLevel::Info,
+            Level::DEBUG => LogLevel::Debug,
+            Level::TRACE => LogLevel::Trace,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Trace => "TRACE",
+        }
+    }
+
+    /// Filter recording the agent at this level and dependencies at `warn` at most
+    fn filter(self) -> EnvFilter {
+        let directive = format!(
+            "{},{}={}",
+            self.min(LogLevel::Warn).label(),
+            AGENT_TARGET,
+            self.label()
+        );
+        EnvFilter::new(directive.to_lowercase())
+    }
+}
+
+/// One log record as shown to support in the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: LogLevel,
+    pub target: String,
+    /// Enclosing spans, outermost first, e.g. `endpoint{name=default}:monitor{tick=retry}`
+    pub spans: String,
+    pub message: String,
+    pub fields: BTreeMap<String, String>,
+}
+
+impl LogEntry {
+    /// The entry laid out like the log file's lines
+    fn to_line(&self) -> String {
+        let mut line = format!("{} {:>5} ", self.timestamp, self.level.label());
+        if !self.spans.is_empty() {
+            let _ = write!(line, "{}: ", self.spans);
+        }
+        let _ = write!(line, "{}: {}", self.target, self.message);
+        for (key, value) in &self.fields {
+            let _ = write!(line, " {}={}", key, value);
+        }
+        line
+    }
+}
+
+/// Collects an event's or span's fields, keeping `message` apart
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields
+                .insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.fields
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+}
+
+/// A span's fields, kept in its registry extensions
+struct SpanFields(BTreeMap<String, String>);
+
+static RECENT_LOGS: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+
+/// Layer keeping the most recent entries in memory for `get_recent_logs`
+struct RecentLogs;
+
+impl<S> Layer<S> for RecentLogs
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attributes: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attributes.record(&mut visitor);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(visitor.fields));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+        if let Some(span) = ctx.span(id) {
+            if let Some(fields) = span.extensions_mut().get_mut::<SpanFields>() {
+                fields.0.extend(visitor.fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut spans = String::new();
+        for span in ctx
+            .event_scope(event)
+            .into_iter()
+            .flat_map(|scope| scope.from_root())
+        {
+            if !spans.is_empty() {
+                spans.push(':');
+            }
+            spans.push_str(span.name());
+            if let Some(SpanFields(fields)) = span.extensions().get::<SpanFields>() {
+                if !fields.is_empty() {
+                    let fields: Vec<String> = fields
+                        .iter()
+                        .map(|(key, value)| format!("{}={}", key, value))
+                        .collect();
+                    let _ = write!(spans, "{{{}}}", fields.join(" "));
+                }
+            }
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let entry = LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+            level: LogLevel::from_level(metadata.level()),
+            target: metadata.target().to_string(),
+            spans,
+            message: visitor.message,
+            fields: visitor.fields,
+        };
+
+        let mut recent = RECENT_LOGS.lock().unwrap();
+        if recent.len() >= MAX_RECENT_LOGS {
+            recent.pop_front();
+        }
+        recent.push_back(entry);
+    }
+}
+
+static LOG_FILE: OnceLock<RollingFileAppender> = OnceLock::new();
+
+/// Writes to the log file once `initialize_log_file` has opened it, and
+/// nowhere before
+struct LogFileWriter;
+
+impl<'a> MakeWriter<'a> for LogFileWriter {
+    type Writer = OptionalWriter<<RollingFileAppender as MakeWriter<'a>>::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        match LOG_FILE.get() {
+            Some(file) => OptionalWriter::some(file.make_writer()),
+            None => OptionalWriter::none(),
+        }
+    }
+}
+
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+static LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+
+/// Installs the agent's subscriber as the global default. Entries are kept in
+/// memory until `initialize_log_file` knows where to write them.
+pub fn initialize_logging() {
+    let (filter, handle) = reload::Layer::new(LogLevel::default().filter());
+    let file = fmt::layer()
+        .with_ansi(false)
+        .with_timer(ChronoUtc::rfc_3339())
+        .with_writer(LogFileWriter);
+    // Debug builds also log to the terminal they were started from
+    let terminal = cfg!(debug_assertions).then(|| fmt::layer().with_writer(std::io::stderr));
+
+    let installed = tracing_subscriber::registry()
+        .with(filter)
+        .with(RecentLogs)
+        .with(file)
+        .with(terminal)
+        .try_init();
+    match installed {
+        Ok(()) => {
+            FILTER.get_or_init(|| handle);
+        }
+        Err(error) => tracing::warn!(%error, "Logging was already initialized"),
+    }
+}
+
+/// Opens the daily log files in the app log directory and writes the entries
+/// logged so far
+pub fn initialize_log_file(app_handle: &tauri::AppHandle) {
+    let dir = match app_handle.path().app_log_dir() {
+        Ok(dir) => dir,
+        Err(error) => {
+            tracing::warn!(%error, "No app log directory; logging to memory only");
+            return;
+        }
+    };
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir);
+    let appender = match appender {
+        Ok(appender) => appender,
+        Err(error) => {
+            tracing::warn!(dir = %dir.display(), %error, "Could not open log file");
+            return;
+        }
+    };
+
+    let early_lines: Vec<String> = {
+        let recent = RECENT_LOGS.lock().unwrap();
+        recent.iter().map(LogEntry::to_line).collect()
+    }; // Mutex guard is dropped here, before anything below can log
+    {
+        let mut writer = appender.make_writer();
+        for line in early_lines {
+            if let Err(error) = writeln!(writer, "{}", line) {
+                tracing::warn!(%error, "Could not write early entries to the log file");
+                break;
+            }
+        }
+    }
+    if LOG_FILE.set(appender).is_ok() {
+        tracing::info!(dir = %dir.display(), "Logging to file");
+    }
+}
+
+/// Changes the most verbose level that is recorded, effective immediately
+pub fn set_log_level(level: LogLevel) {
+    let Some(handle) = FILTER.get() else {
+        return;
+    };
+    if LEVEL.swap(level as u8, Ordering::Relaxed) == level as u8 {
+        return;
+    }
+    match handle.reload(level.filter()) {
+        Ok(()) => tracing::info!(level = level.label(), "Log level changed"),
+        Err(error) => tracing::warn!(%error, "Could not change the log level"),
+    }
+}
+
+/// Tauri command to get the most recent log entries, oldest first, optionally
+/// only those at `level` or more severe
+#[tauri::command]
+pub async fn get_recent_logs(limit: Option<usize>, level: Option<LogLevel>) -> Vec<LogEntry> {
+    let recent = RECENT_LOGS.lock().unwrap();
+    let mut entries: Vec<LogEntry> = recent
+        .iter()
+        .rev()
+        .filter(|entry| level.is_none_or(|level| entry.level <= level))
+        .take(limit.unwrap_or(MAX_RECENT_LOGS))
+        .cloned()
+        .collect();
+    entries.reverse();
+    entries
+}
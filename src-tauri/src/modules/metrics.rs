@@ -0,0 +1,277 @@
+use bollard::query_parameters::ListContainersOptions;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::Manager;
+use thiserror::Error;
+
+use super::alerts;
+use super::config::{self, MetricsSettings};
+use super::cpu_sampler;
+use super::docker::{self, get_connection_timeout, get_docker_client};
+use super::docker_stats;
+use super::metrics_store::{MetricPoint, MetricResolution, MetricRow, MetricsStore, Summary};
+use super::sys_info::{get_gpu_info, get_storage_info, read_memory_info, storage_size_to_bytes};
+
+const HOUR_MS: i64 = 60 * 60 * 1000;
+const DAY_MS: i64 = 24 * HOUR_MS;
+const GB_IN_BYTES: f64 = 1024.0 * 1024.0 * 1024.0;
+
+// Recorded metrics. Per-device and per-container series carry the device or
+// container in brackets, e.g. `disk.used_percent[/home]` or `gpu.temperature_celsius[0]`.
+const CPU_USAGE: &str = "cpu.usage_percent";
+const MEMORY_USED: &str = "memory.used_gb";
+const MEMORY_USED_PERCENT: &str = "memory.used_percent";
+const DISK_USED_PERCENT: &str = "disk.used_percent";
+const DISK_FREE: &str = "disk.free_gb";
+const GPU_UTILIZATION: &str = "gpu.utilization_percent";
+const GPU_MEMORY_USED: &str = "gpu.memory_used_gb";
+const GPU_TEMPERATURE: &str = "gpu.temperature_celsius";
+const CONTAINERS_TOTAL: &str = "containers.total";
+const CONTAINERS_RUNNING: &str = "containers.running";
+const CONTAINER_RUNNING: &str = "container.running";
+const CONTAINER_CPU: &str = "container.cpu_percent";
+const CONTAINER_MEMORY_PERCENT: &str = "container.memory_percent";
+
+#[derive(Error, Debug, Clone, Serialize)]
+pub enum MetricsError {
+    #[error("Invalid time range: {0}")]
+    InvalidRange(String),
+    #[error("Metrics storage error: {0}")]
+    Storage(String),
+}
+
+fn labeled(metric: &str, label: &str) -> String {
+    format!("{}[{}]", metric, label)
+}
+
+/// Retention of the raw, minute and hour resolutions in milliseconds
+fn retention(settings: &MetricsSettings) -> [i64; 3] {
+    [
+        settings.raw_retention_hours as i64 * HOUR_MS,
+        settings.minute_retention_days as i64 * DAY_MS,
+        settings.hour_retention_days as i64 * DAY_MS,
+    ]
+}
+
+static METRICS_STORE: std::sync::OnceLock<Arc<Mutex<MetricsStore>>> = std::sync::OnceLock::new();
+
+fn get_metrics_store() -> Arc<Mutex<MetricsStore>> {
+    METRICS_STORE
+        .get_or_init(|| {
+            let settings = config::current_config().metrics;
+            let store = MetricsStore::in_memory(retention(&settings))
+                .expect("an in-memory database always opens");
+            Arc::new(Mutex::new(store))
+        })
+        .clone()
+}
+
+/// Host metrics from the CPU sampler, sysinfo, NVML and the mounted disks. Blocking.
+fn collect_system_metrics() -> BTreeMap<String, f64> {
+    let mut values = BTreeMap::new();
+
+    if let Some(usage) = cpu_sampler::latest_cpu_info().and_then(|cpu| cpu.usage_percent) {
+        values.insert(CPU_USAGE.to_string(), usage as f64);
+    }
+    if let Ok(memory) = read_memory_info() {
+        values.insert(MEMORY_USED.to_string(), memory.used_gb);
+        if memory.total_gb > 0.0 {
+            values.insert(
+                MEMORY_USED_PERCENT.to_string(),
+                memory.used_gb / memory.total_gb * 100.0,
+            );
+        }
+    }
+
+    if let Ok(storage) = get_storage_info() {
+        for device in storage.devices {
+            let total = storage_size_to_bytes(device.total_size, &device.unit);
+            let used = storage_size_to_bytes(device.used_size, &device.unit);
+            let available = storage_size_to_bytes(device.available_size, &device.unit);
+            values.insert(
+                labeled(DISK_FREE, &device.mount_point),
+                available as f64 / GB_IN_BYTES,
+            );
+            if total > 0 {
+                values.insert(
+                    labeled(DISK_USED_PERCENT, &device.mount_point),
+                    used as f64 / total as f64 * 100.0,
+                );
+            }
+        }
+    }
+
+    // Machines without an NVIDIA GPU simply have no GPU series
+    if let Ok(gpus) = get_gpu_info() {
+        for (index, gpu) in gpus.iter().enumerate() {
+            let index = index.to_string();
+            if let Some(utilization) = gpu.utilization_percent {
+                values.insert(labeled(GPU_UTILIZATION, &index), utilization as f64);
+            }
+            if let Some(memory_used) = gpu.memory_used_gb {
+                values.insert(labeled(GPU_MEMORY_USED, &index), memory_used);
+            }
+            if let Some(temperature) = gpu.temperature_celsius {
+                values.insert(labeled(GPU_TEMPERATURE, &index), temperature as f64);
+            }
+        }
+    }
+
+    values
+}
+
+/// Container counts on the active endpoint and the latest per-container stats
+async fn collect_container_metrics() -> BTreeMap<String, f64> {
+    let mut values = BTreeMap::new();
+    let Ok(docker) = get_docker_client() else {
+        return values;
+    };
+
+    let containers = tokio::time::timeout(
+        get_connection_timeout(),
+        docker.list_containers(Some(ListContainersOptions {
+            all: true,
+            ..Default::default()
+        })),
+    )
+    .await;
+    match containers {
+        Ok(Ok(containers)) => {
+            let mut running = 0;
+            for container in &containers {
+                let is_running = container
+                    .state
+                    .as_ref()
+                    .is_some_and(|s| s.to_string() == "running");
+                running += usize::from(is_running);
+                // Series are keyed by name so they survive the container being recreated
+                for name in container.names.iter().flatten() {
+                    values.insert(
+                        labeled(CONTAINER_RUNNING, name.trim_start_matches('/')),
+                        if is_running { 1.0 } else { 0.0 },
+                    );
+                }
+            }
+            values.insert(CONTAINERS_TOTAL.to_string(), containers.len() as f64);
+            values.insert(CONTAINERS_RUNNING.to_string(), running as f64);
+        }
+        Ok(Err(error)) => tracing::debug!(%error, "Could not list containers for metrics"),
+        Err(_) => tracing::debug!("Listing containers for metrics timed out"),
+    }
+
+    for stats in docker_stats::latest_container_stats() {
+        values.insert(labeled(CONTAINER_CPU, &stats.name), stats.cpu_percent);
+        values.insert(
+            labeled(CONTAINER_MEMORY_PERCENT, &stats.name),
+            stats.memory_percent,
+        );
+    }
+    values
+}
+
+// Sample all metrics at the configured interval and evaluate alert rules on
+// each sample, picking up config changes
+async fn metrics_sampler_loop(app_handle: tauri::AppHandle) {
+    let mut settings = config::current_config().metrics;
+    let mut config_updates = config::subscribe_to_config();
+    let sample_interval = |settings: &MetricsSettings| {
+        let period = Duration::from_secs(settings.sample_interval_secs);
+        // Skip the immediate first tick so the CPU sampler has a reading
+        tokio::time::interval_at(tokio::time::Instant::now() + period, period)
+    };
+    let mut interval = sample_interval(&settings);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if docker::is_monitoring_paused() {
+                    continue;
+                }
+                let mut values = tauri::async_runtime::spawn_blocking(collect_system_metrics)
+                    .await
+                    .unwrap_or_else(|error| {
+                        tracing::error!(%error, "Metrics collection panicked");
+                        BTreeMap::new()
+                    });
+                values.extend(collect_container_metrics().await);
+
+                let row = MetricRow {
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                    values: values
+                        .into_iter()
+                        .map(|(metric, value)| (metric, Summary::single(value)))
+                        .collect(),
+                };
+                let recorded = row.clone();
+                let stored = tauri::async_runtime::spawn_blocking(move || {
+                    get_metrics_store().lock().unwrap().record(&recorded)
+                })
+                .await;
+                match stored {
+                    Ok(Ok(())) => {}
+                    Ok(Err(error)) => tracing::warn!(%error, "Could not record metrics"),
+                    Err(error) => tracing::error!(%error, "Recording metrics panicked"),
+                }
+                alerts::evaluate_alerts(&app_handle, &row);
+            }
+            Ok(()) = config_updates.changed() => {
+                let new_settings = config_updates.borrow_and_update().metrics.clone();
+                if new_settings.sample_interval_secs != settings.sample_interval_secs {
+                    interval = sample_interval(&new_settings);
+                }
+                let store = get_metrics_store();
+                store.lock().unwrap().set_retention(retention(&new_settings));
+                settings = new_settings;
+            }
+        }
+    }
+}
+
+/// Opens the metrics database in the app data directory and starts sampling
+pub fn initialize_metrics(app_handle: &tauri::AppHandle) {
+    let settings = config::current_config().metrics;
+    match app_handle.path().app_data_dir() {
+        Ok(dir) => match MetricsStore::open(&dir.join("metrics"), retention(&settings)) {
+            Ok(store) => {
+                *get_metrics_store().lock().unwrap() = store;
+            }
+            Err(error) => {
+                tracing::warn!(%error, "Could not open metrics store; keeping metrics in memory")
+            }
+        },
+        Err(error) => tracing::warn!(%error, "No app data directory; keeping metrics in memory"),
+    }
+
+    tauri::async_runtime::spawn(metrics_sampler_loop(app_handle.clone()));
+}
+
+/// Tauri command to get one metric's history between `from` and `to`, given
+/// in milliseconds since the Unix epoch. Without a resolution, the finest one
+/// that still covers `from` is used.
+#[tauri::command]
+pub async fn query_metrics(
+    metric: String,
+    from: i64,
+    to: i64,
+    resolution: Option<MetricResolution>,
+) -> Result<Vec<MetricPoint>, MetricsError> {
+    if from > to {
+        return Err(MetricsError::InvalidRange(
+            "`from` must not be after `to`".to_string(),
+        ));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = get_metrics_store();
+        let store_guard = store.lock().unwrap();
+        let resolution = resolution.unwrap_or_else(|| {
+            store_guard.resolution_for(from, chrono::Utc::now().timestamp_millis())
+        });
+        store_guard.query(&metric, from, to, resolution)
+    })
+    .await
+    .map_err(|error| MetricsError::Storage(error.to_string()))?
+    .map_err(|error| MetricsError::Storage(error.to_string()))
+}
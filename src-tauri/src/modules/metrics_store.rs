@@ -0,0 +1,303 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const MINUTE_MS: i64 = 60 * 1000;
+const HOUR_MS: i64 = 60 * MINUTE_MS;
+const DATABASE_FILE_NAME: &str = "metrics.db";
+
+/// Rows keyed by resolution, metric and timestamp; downsampled rows are
+/// stored under their bucket start and updated while the bucket fills
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS samples (
+        resolution TEXT NOT NULL,
+        metric TEXT NOT NULL,
+        timestamp INTEGER NOT NULL,
+        mean REAL NOT NULL,
+        min REAL NOT NULL,
+        max REAL NOT NULL,
+        count INTEGER NOT NULL,
+        PRIMARY KEY (resolution, metric, timestamp)
+    ) WITHOUT ROWID;
+    CREATE INDEX IF NOT EXISTS samples_by_time ON samples (resolution, timestamp);
+";
+
+/// Adds a summary to its row, merging it into the bucket's running summary
+const UPSERT: &str = "
+    INSERT INTO samples (resolution, metric, timestamp, mean, min, max, count)
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+    ON CONFLICT (resolution, metric, timestamp) DO UPDATE SET
+        mean = (mean * count + excluded.mean * excluded.count) / (count + excluded.count),
+        min = MIN(min, excluded.min),
+        max = MAX(max, excluded.max),
+        count = count + excluded.count
+";
+
+/// Granularity of stored samples, from finest to coarsest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricResolution {
+    /// Every sample, at the configured sampling interval
+    Raw,
+    /// One-minute averages
+    Minute,
+    /// One-hour averages
+    Hour,
+}
+
+impl MetricResolution {
+    const ALL: [MetricResolution; 3] = [
+        MetricResolution::Raw,
+        MetricResolution::Minute,
+        MetricResolution::Hour,
+    ];
+
+    /// Start of the row a sample taken at `timestamp` belongs to
+    fn bucket_start(self, timestamp: i64) -> i64 {
+        match self {
+            MetricResolution::Raw => timestamp,
+            MetricResolution::Minute => timestamp - timestamp.rem_euclid(MINUTE_MS),
+            MetricResolution::Hour => timestamp - timestamp.rem_euclid(HOUR_MS),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            MetricResolution::Raw => "raw",
+            MetricResolution::Minute => "minute",
+            MetricResolution::Hour => "hour",
+        }
+    }
+}
+
+/// Mean, minimum and maximum of the samples folded into one row
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Summary {
+    pub(crate) mean: f64,
+    pub(crate) min: f64,
+    pub(crate) max: f64,
+    pub(crate) count: u32,
+}
+
+impl Summary {
+    pub(crate) fn single(value: f64) -> Self {
+        Self {
+            mean: value,
+            min: value,
+            max: value,
+            count: 1,
+        }
+    }
+}
+
+/// Every metric recorded at one point in time
+#[derive(Debug, Clone)]
+pub(crate) struct MetricRow {
+    /// Milliseconds since the Unix epoch
+    pub(crate) timestamp: i64,
+    pub(crate) values: BTreeMap<String, Summary>,
+}
+
+/// One point of a queried series
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricPoint {
+    pub timestamp: i64,
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Embedded time-series store on SQLite: each sample is kept as a raw row and
+/// folded into the minute and hour rows of its buckets, and each resolution
+/// keeps its own retention window. Buckets still being filled are stored like
+/// any other row, so they survive a restart.
+pub(crate) struct MetricsStore {
+    connection: Connection,
+    /// Retention of the raw, minute and hour resolutions in milliseconds
+    retention: [i64; 3],
+}
+
+impl MetricsStore {
+    fn with_connection(connection: Connection, retention: [i64; 3]) -> rusqlite::Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection,
+            retention,
+        })
+    }
+
+    /// A store that only keeps samples in memory. `retention` is in
+    /// milliseconds for the raw, minute and hour resolutions.
+    pub(crate) fn in_memory(retention: [i64; 3]) -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?, retention)
+    }
+
+    /// Opens or creates the database in `directory` and drops expired rows
+    pub(crate) fn open(directory: &Path, retention: [i64; 3]) -> rusqlite::Result<Self> {
+        if let Err(error) = std::fs::create_dir_all(directory) {
+            tracing::warn!(%error, "Could not create the metrics directory");
+        }
+        let connection = Connection::open(directory.join(DATABASE_FILE_NAME))?;
+        // Samples are cheap to lose on power failure, slow fsyncs are not
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.pragma_update(None, "synchronous", "NORMAL")?;
+        let store = Self::with_connection(connection, retention)?;
+        store.expire(chrono::Utc::now().timestamp_millis())?;
+        Ok(store)
+    }
+
+    /// Updates how long each resolution is kept
+    pub(crate) fn set_retention(&mut self, retention: [i64; 3]) {
+        self.retention = retention;
+    }
+
+    /// Records one sample at every resolution and drops expired rows
+    pub(crate) fn record(&mut self, row: &MetricRow) -> rusqlite::Result<()> {
+        let transaction = self.connection.transaction()?;
+        {
+            let mut upsert = transaction.prepare_cached(UPSERT)?;
+            for resolution in MetricResolution::ALL {
+                let timestamp = resolution.bucket_start(row.timestamp);
+                for (metric, summary) in &row.values {
+                    upsert.execute(params![
+                        resolution.name(),
+                        metric,
+                        timestamp,
+                        summary.mean,
+                        summary.min,
+                        summary.max,
+                        summary.count,
+                    ])?;
+                }
+            }
+        }
+        transaction.commit()?;
+        self.expire(row.timestamp)
+    }
+
+    fn expire(&self, now: i64) -> rusqlite::Result<()> {
+        let mut delete = self
+            .connection
+            .prepare_cached("DELETE FROM samples WHERE resolution = ?1 AND timestamp < ?2")?;
+        for (resolution, retention_ms) in MetricResolution::ALL.iter().zip(self.retention) {
+            // Keep a bucket until all of it has left the window
+            let cutoff = resolution.bucket_start(now - retention_ms);
+            delete.execute(params![resolution.name(), cutoff])?;
+        }
+        Ok(())
+    }
+
+    /// The finest resolution that still holds data as old as `from`
+    pub(crate) fn resolution_for(&self, from: i64, now: i64) -> MetricResolution {
+        MetricResolution::ALL
+            .into_iter()
+            .zip(self.retention)
+            .find(|(_, retention_ms)| now - retention_ms <= from)
+            .map_or(MetricResolution::Hour, |(resolution, _)| resolution)
+    }
+
+    /// Values of `metric` between `from` and `to` (inclusive) at `resolution`,
+    /// including the bucket still being filled
+    pub(crate) fn query(
+        &self,
+        metric: &str,
+        from: i64,
+        to: i64,
+        resolution: MetricResolution,
+    ) -> rusqlite::Result<Vec<MetricPoint>> {
+        let mut select = self.connection.prepare_cached(
+            "SELECT timestamp, mean, min, max FROM samples
+             WHERE resolution = ?1 AND metric = ?2 AND timestamp BETWEEN ?3 AND ?4
+             ORDER BY timestamp",
+        )?;
+        let points = select.query_map(params![resolution.name(), metric, from, to], |row| {
+            Ok(MetricPoint {
+                timestamp: row.get(0)?,
+                value: row.get(1)?,
+                min: row.get(2)?,
+                max: row.get(3)?,
+            })
+        })?;
+        points.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RETENTION: [i64; 3] = [HOUR_MS, 24 * HOUR_MS, 30 * 24 * HOUR_MS];
+
+    fn sample(timestamp: i64, value: f64) -> MetricRow {
+        MetricRow {
+            timestamp,
+            values: BTreeMap::from([("cpu.usage_percent".to_string(), Summary::single(value))]),
+        }
+    }
+
+    fn values(store: &MetricsStore, resolution: MetricResolution) -> Vec<(i64, f64, f64, f64)> {
+        store
+            .query("cpu.usage_percent", 0, i64::MAX, resolution)
+            .unwrap()
+            .into_iter()
+            .map(|point| (point.timestamp, point.value, point.min, point.max))
+            .collect()
+    }
+
+    #[test]
+    fn folds_samples_into_buckets() {
+        let mut store = MetricsStore::in_memory(RETENTION).unwrap();
+        let start = 1_700_000_000_000 - 1_700_000_000_000 % HOUR_MS;
+        for (offset, value) in [(0, 10.0), (20_000, 20.0), (40_000, 60.0), (MINUTE_MS, 40.0)] {
+            store.record(&sample(start + offset, value)).unwrap();
+        }
+
+        assert_eq!(values(&store, MetricResolution::Raw).len(), 4);
+        assert_eq!(
+            values(&store, MetricResolution::Minute),
+            [
+                (start, 30.0, 10.0, 60.0),
+                (start + MINUTE_MS, 40.0, 40.0, 40.0)
+            ]
+        );
+        assert_eq!(
+            values(&store, MetricResolution::Hour),
+            [(start, 32.5, 10.0, 60.0)]
+        );
+    }
+
+    #[test]
+    fn expires_each_resolution_separately() {
+        let mut store = MetricsStore::in_memory(RETENTION).unwrap();
+        let start = 1_700_000_000_000 - 1_700_000_000_000 % HOUR_MS;
+        store.record(&sample(start, 10.0)).unwrap();
+        store.record(&sample(start + 2 * HOUR_MS, 20.0)).unwrap();
+
+        assert_eq!(values(&store, MetricResolution::Raw).len(), 1);
+        assert_eq!(values(&store, MetricResolution::Minute).len(), 2);
+        assert_eq!(
+            store.resolution_for(start, start + 2 * HOUR_MS),
+            MetricResolution::Minute
+        );
+    }
+
+    #[test]
+    fn keeps_open_buckets_across_restarts() {
+        let directory =
+            std::env::temp_dir().join(format!("desktop-agent-metrics-{}", std::process::id()));
+        let start = 1_700_000_000_000 - 1_700_000_000_000 % HOUR_MS;
+        let now = chrono::Utc::now().timestamp_millis();
+        let retention = [now, now, now];
+        {
+            let mut store = MetricsStore::open(&directory, retention).unwrap();
+            store.record(&sample(start, 10.0)).unwrap();
+        }
+        let mut store = MetricsStore::open(&directory, retention).unwrap();
+        store.record(&sample(start + 1000, 30.0)).unwrap();
+        let minute = values(&store, MetricResolution::Minute);
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(minute, [(start, 20.0, 10.0, 30.0)]);
+    }
+}
@@ -1,2 +1,29 @@
+pub mod alerts;
+pub mod config;
+pub mod cpu_sampler;
 pub mod docker;
+pub mod docker_compose;
+pub mod docker_containers;
+pub mod docker_desktop;
+pub mod docker_disk_usage;
+pub mod docker_endpoints;
+pub mod docker_exec;
+pub mod docker_health;
+pub mod docker_images;
+pub mod docker_logs;
+pub mod docker_networks;
+pub mod docker_stats;
+pub mod docker_updates;
+pub mod docker_volumes;
+pub mod logging;
+pub mod metrics;
+pub mod metrics_store;
+pub mod notifications;
+pub mod platform;
+pub mod prometheus;
 pub mod sys_info;
+#[cfg(test)]
+mod test_support;
+pub mod tray;
+pub mod version;
+pub mod webhooks;
@@ -0,0 +1,468 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::alerts::{Alert, AlertSeverity};
+use super::config::{self, NotificationSettings};
+use super::docker::DockerStatus;
+use super::docker_health::{ContainerAlert, ContainerAlertKind};
+use super::platform::{CommandRunner, Platform, SystemCommandRunner};
+use super::webhooks::{self, WebhookEvent};
+
+const APP_NAME: &str = "Desktop Agent";
+/// Number of notifications kept while in test mode
+const MAX_RECORDED_NOTIFICATIONS: usize = 200;
+
+/// Kinds of notification that can be muted separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    /// Docker stopped or came back on an endpoint
+    DockerState,
+    /// A newer Docker Engine or Docker Desktop release is available
+    UpdateAvailable,
+    /// A container exited with an error, became unhealthy or keeps restarting
+    ContainerCrashed,
+    /// An alert rule fired
+    Alert,
+}
+
+/// A notification as shown to the user, or recorded in test mode
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    pub category: NotificationCategory,
+    pub title: String,
+    pub body: String,
+    pub timestamp: String,
+}
+
+#[derive(Default)]
+struct NotificationState {
+    /// When each deduplication key last produced a notification
+    last_sent: HashMap<String, Instant>,
+    /// Send times within the last minute, for the global limit
+    recent_sends: VecDeque<Instant>,
+    recorded: VecDeque<Notification>,
+    /// Last running state seen per endpoint
+    docker_running: HashMap<String, bool>,
+    /// Endpoints we reported as stopped, so recovery is only reported after a stop
+    stopped_endpoints: HashSet<String>,
+    /// Update versions already announced
+    announced_updates: HashSet<String>,
+}
+
+static NOTIFICATION_STATE: std::sync::OnceLock<Arc<Mutex<NotificationState>>> =
+    std::sync::OnceLock::new();
+
+fn get_notification_state() -> Arc<Mutex<NotificationState>> {
+    NOTIFICATION_STATE
+        .get_or_init(|| Arc::new(Mutex::new(NotificationState::default())))
+        .clone()
+}
+
+fn escape_applescript(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_powershell(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+/// Shows a notification with the platform's own tooling
+fn show(runner: &dyn CommandRunner, platform: Platform, notification: &Notification) -> bool {
+    let title = notification.title.as_str();
+    let body = notification.body.as_str();
+    match platform {
+        Platform::Linux => runner
+            .run(
+                "notify-send",
+                &[&format!("--app-name={}", APP_NAME), title, body],
+            )
+            .is_some(),
+        Platform::MacOs => runner
+            .run(
+                "osascript",
+                &[
+                    "-e",
+                    &format!(
+                        "display notification \"{}\" with title \"{}\"",
+                        escape_applescript(body),
+                        escape_applescript(title)
+                    ),
+                ],
+            )
+            .is_some(),
+        // A tray balloon, which Windows 10 and later show as a toast. The
+        // script stays up while the balloon shows, so it is not waited for.
+        Platform::Windows => runner.spawn(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                &format!(
+                    "Add-Type -AssemblyName System.Windows.Forms; \
+                     $n = New-Object System.Windows.Forms.NotifyIcon; \
+                     $n.Icon = [System.Drawing.SystemIcons]::Information; \
+                     $n.Visible = $true; \
+                     $n.ShowBalloonTip(10000, '{}', '{}', 'Info'); \
+                     Start-Sleep -Seconds 10; $n.Dispose()",
+                    escape_powershell(title),
+                    escape_powershell(body)
+                ),
+            ],
+        ),
+    }
+}
+
+impl NotificationState {
+    /// Applies muting, the cooldown for `key` and the rate limit at `now`.
+    /// Returns the notification if it should be shown; in test mode it is
+    /// recorded instead.
+    fn admit(
+        &mut self,
+        settings: &NotificationSettings,
+        key: &str,
+        notification: Notification,
+        now: Instant,
+    ) -> Option<Notification> {
+        if !settings.enabled || settings.muted.contains(&notification.category) {
+            return None;
+        }
+
+        let cooldown = Duration::from_secs(settings.cooldown_secs);
+        if self
+            .last_sent
+            .get(key)
+            .is_some_and(|sent| now.duration_since(*sent) < cooldown)
+        {
+            tracing::debug!(key, "Notification suppressed by cooldown");
+            return None;
+        }
+        while self
+            .recent_sends
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= Duration::from_secs(60))
+        {
+            self.recent_sends.pop_front();
+        }
+        if self.recent_sends.len() >= settings.max_per_minute as usize {
+            tracing::debug!(key, "Notification suppressed by rate limit");
+            return None;
+        }
+        self.last_sent.insert(key.to_string(), now);
+        self.recent_sends.push_back(now);
+
+        if settings.test_mode {
+            if self.recorded.len() >= MAX_RECORDED_NOTIFICATIONS {
+                self.recorded.pop_front();
+            }
+            self.recorded.push_back(notification);
+            return None;
+        }
+        Some(notification)
+    }
+}
+
+/// Publishes an event to the webhooks, then shows it as a notification unless
+/// its category is muted or it is rate limited. `key` identifies repeats of
+/// the same notification, which are suppressed for the configured cooldown.
+fn dispatch(
+    category: NotificationCategory,
+    severity: AlertSeverity,
+    key: &str,
+    title: String,
+    body: String,
+) {
+    webhooks::publish(WebhookEvent::new(category, severity, &title, &body));
+
+    let settings = config::current_config().notifications;
+    let notification = Notification {
+        category,
+        title,
+        body,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+    let admitted = {
+        let state = get_notification_state();
+        let mut state_guard = state.lock().unwrap();
+        state_guard.admit(&settings, key, notification, Instant::now())
+    }; // Mutex guard is dropped here
+    let Some(notification) = admitted else {
+        return;
+    };
+
+    // The helpers can take a while to return, so keep them off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        if !show(&SystemCommandRunner, Platform::current(), &notification) {
+            tracing::warn!(title = %notification.title, "Could not show notification");
+        }
+    });
+}
+
+/// Notifies when an endpoint's daemon stops or comes back after a stop, and
+/// when a new update is first seen
+pub(crate) fn observe_docker_status(status: &DockerStatus) {
+    let (stopped, recovered, updates) = {
+        let state = get_notification_state();
+        let mut state_guard = state.lock().unwrap();
+        let was_running = state_guard
+            .docker_running
+            .insert(status.endpoint.clone(), status.is_running);
+
+        let stopped = was_running == Some(true) && !status.is_running;
+        if stopped {
+            state_guard
+                .stopped_endpoints
+                .insert(status.endpoint.clone());
+        }
+        let recovered = status.is_running && state_guard.stopped_endpoints.remove(&status.endpoint);
+
+        let mut updates = Vec::new();
+        for (product, update) in [
+            ("Docker Engine", &status.engine_update),
+            ("Docker Desktop", &status.desktop_update),
+        ] {
+            if let Some(update) = update.as_ref().filter(|u| u.update_available) {
+                let key = format!("update:{}:{}", product, update.latest_version);
+                if state_guard.announced_updates.insert(key.clone()) {
+                    updates.push((key, product, update.clone()));
+                }
+            }
+        }
+        (stopped, recovered, updates)
+    };
+
+    if stopped {
+        dispatch(
+            NotificationCategory::DockerState,
+            AlertSeverity::Critical,
+            &format!("docker:{}:stopped", status.endpoint),
+            "Docker stopped".to_string(),
+            format!(
+                "Docker on {} is no longer running: {}",
+                status.endpoint,
+                status.error.as_deref().unwrap_or("not responding")
+            ),
+        );
+    }
+    if recovered {
+        dispatch(
+            NotificationCategory::DockerState,
+            AlertSeverity::Info,
+            &format!("docker:{}:running", status.endpoint),
+            "Docker is running".to_string(),
+            format!("Docker on {} is running again", status.endpoint),
+        );
+    }
+    for (key, product, update) in updates {
+        dispatch(
+            NotificationCategory::UpdateAvailable,
+            AlertSeverity::Info,
+            &key,
+            format!("{} update available", product),
+            format!(
+                "{} {} is available (installed: {})",
+                product, update.latest_version, update.current_version
+            ),
+        );
+    }
+}
+
+/// Notifies about a crashed, unhealthy or restart-looping container
+pub(crate) fn notify_container_alert(alert: &ContainerAlert) {
+    let (title, kind) = match alert.kind {
+        ContainerAlertKind::Unhealthy => ("Container unhealthy", "unhealthy"),
+        ContainerAlertKind::NonZeroExit { .. } => ("Container crashed", "exit"),
+        ContainerAlertKind::RestartLoop { .. } => ("Container restarting repeatedly", "restart"),
+    };
+    dispatch(
+        NotificationCategory::ContainerCrashed,
+        AlertSeverity::Warning,
+        &format!("container:{}:{}", alert.container_name, kind),
+        title.to_string(),
+        alert.message.clone(),
+    );
+}
+
+/// Notifies that an alert rule fired
+pub(crate) fn notify_alert(alert: &Alert) {
+    dispatch(
+        NotificationCategory::Alert,
+        alert.severity,
+        &format!("alert:{}", alert.key),
+        alert.rule_name.clone(),
+        alert.message.clone(),
+    );
+}
+
+/// Tauri command to get the notifications recorded in test mode, oldest first
+#[tauri::command]
+pub async fn get_recorded_notifications() -> Vec<Notification> {
+    let state = get_notification_state();
+    let state_guard = state.lock().unwrap();
+    state_guard.recorded.iter().cloned().collect()
+}
+
+/// Tauri command to discard the notifications recorded in test mode
+#[tauri::command]
+pub async fn clear_recorded_notifications() {
+    let state = get_notification_state();
+    let mut state_guard = state.lock().unwrap();
+    state_guard.recorded.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(cooldown_secs: u64, max_per_minute: u32) -> NotificationSettings {
+        NotificationSettings {
+            enabled: true,
+            muted: Vec::new(),
+            cooldown_secs,
+            max_per_minute,
+            test_mode: true,
+        }
+    }
+
+    fn notification(category: NotificationCategory, title: &str) -> Notification {
+        Notification {
+            category,
+            title: title.to_string(),
+            body: format!("{} body", title),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Offers an alert notification under `key` at `start + offset_secs` and
+    /// returns how many notifications are recorded afterwards
+    fn offer(
+        state: &mut NotificationState,
+        settings: &NotificationSettings,
+        key: &str,
+        start: Instant,
+        offset_secs: u64,
+    ) -> usize {
+        let now = start + Duration::from_secs(offset_secs);
+        let shown = state.admit(
+            settings,
+            key,
+            notification(NotificationCategory::Alert, key),
+            now,
+        );
+        assert!(shown.is_none(), "test mode records instead of showing");
+        state.recorded.len()
+    }
+
+    #[test]
+    fn suppresses_repeats_within_the_cooldown() {
+        let settings = settings(300, 60);
+        let mut state = NotificationState::default();
+        let start = Instant::now();
+
+        assert_eq!(offer(&mut state, &settings, "alert:a", start, 0), 1);
+        assert_eq!(offer(&mut state, &settings, "alert:a", start, 299), 1);
+        // Other keys are not held back by this one's cooldown
+        assert_eq!(offer(&mut state, &settings, "alert:b", start, 10), 2);
+        assert_eq!(offer(&mut state, &settings, "alert:a", start, 300), 3);
+    }
+
+    #[test]
+    fn limits_notifications_per_minute() {
+        let settings = settings(0, 2);
+        let mut state = NotificationState::default();
+        let start = Instant::now();
+
+        assert_eq!(offer(&mut state, &settings, "alert:a", start, 0), 1);
+        assert_eq!(offer(&mut state, &settings, "alert:b", start, 10), 2);
+        assert_eq!(offer(&mut state, &settings, "alert:c", start, 20), 2);
+        // The first send has left the window
+        assert_eq!(offer(&mut state, &settings, "alert:c", start, 60), 3);
+        assert_eq!(offer(&mut state, &settings, "alert:d", start, 61), 3);
+    }
+
+    #[test]
+    fn drops_muted_categories_and_disabled_notifications() {
+        let mut settings = settings(0, 60);
+        settings.muted = vec![NotificationCategory::UpdateAvailable];
+        let mut state = NotificationState::default();
+        let now = Instant::now();
+
+        let update = notification(NotificationCategory::UpdateAvailable, "Update");
+        assert!(state.admit(&settings, "update", update, now).is_none());
+        let crash = notification(NotificationCategory::ContainerCrashed, "Crash");
+        assert!(state.admit(&settings, "crash", crash, now).is_none());
+        assert_eq!(state.recorded.len(), 1);
+        assert_eq!(state.recorded[0].title, "Crash");
+
+        settings.enabled = false;
+        let alert = notification(NotificationCategory::Alert, "Alert");
+        assert!(state.admit(&settings, "alert", alert, now).is_none());
+        assert_eq!(state.recorded.len(), 1);
+    }
+
+    #[test]
+    fn shows_notifications_outside_test_mode() {
+        let mut settings = settings(0, 60);
+        settings.test_mode = false;
+        let mut state = NotificationState::default();
+
+        let alert = notification(NotificationCategory::Alert, "Alert");
+        let shown = state.admit(&settings, "alert", alert, Instant::now());
+        assert_eq!(shown.map(|n| n.title).as_deref(), Some("Alert"));
+        assert!(state.recorded.is_empty());
+    }
+
+    #[test]
+    fn keeps_only_the_latest_recorded_notifications() {
+        let settings = settings(0, 60);
+        let mut state = NotificationState::default();
+        let start = Instant::now();
+
+        // Spread over minutes so the rate limit does not get in the way
+        for index in 0..MAX_RECORDED_NOTIFICATIONS as u64 + 1 {
+            offer(
+                &mut state,
+                &settings,
+                &format!("alert:{}", index),
+                start,
+                index * 60,
+            );
+        }
+        assert_eq!(state.recorded.len(), MAX_RECORDED_NOTIFICATIONS);
+        assert_eq!(state.recorded[0].title, "alert:1");
+    }
+
+    /// Records which programs were run and which were only started
+    #[derive(Default)]
+    struct RecordingRunner(Mutex<Vec<(&'static str, String)>>);
+
+    impl CommandRunner for RecordingRunner {
+        fn run(&self, program: &str, _args: &[&str]) -> Option<String> {
+            self.0.lock().unwrap().push(("run", program.to_string()));
+            Some(String::new())
+        }
+
+        fn spawn(&self, program: &str, _args: &[&str]) -> bool {
+            self.0.lock().unwrap().push(("spawn", program.to_string()));
+            true
+        }
+    }
+
+    #[test]
+    fn does_not_wait_for_the_windows_balloon() {
+        let alert = notification(NotificationCategory::Alert, "Alert");
+        for (platform, expected) in [
+            (Platform::Linux, ("run", "notify-send")),
+            (Platform::MacOs, ("run", "osascript")),
+            (Platform::Windows, ("spawn", "powershell")),
+        ] {
+            let runner = RecordingRunner::default();
+            assert!(show(&runner, platform, &alert));
+            let calls = runner.0.into_inner().unwrap();
+            assert_eq!(calls, [(expected.0, expected.1.to_string())]);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::process::{Command, Stdio};
+
+/// Operating system the agent runs on, passed explicitly so platform-specific
+/// code can be exercised on any host
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Platform {
+    Windows,
+    MacOs,
+    Linux,
+}
+
+impl Platform {
+    pub(crate) fn current() -> Self {
+        if cfg!(target_os = "windows") {
+            Platform::Windows
+        } else if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else {
+            Platform::Linux
+        }
+    }
+}
+
+/// Runs external programs, so their output can be faked
+pub(crate) trait CommandRunner: Send + Sync {
+    /// Returns stdout when the program ran and exited successfully
+    fn run(&self, program: &str, args: &[&str]) -> Option<String>;
+
+    /// Starts the program without waiting for it to exit, returning whether
+    /// it started
+    fn spawn(&self, program: &str, args: &[&str]) -> bool {
+        self.run(program, args).is_some()
+    }
+}
+
+/// Runs commands on the host
+pub(crate) struct SystemCommandRunner;
+
+impl CommandRunner for SystemCommandRunner {
+    fn run(&self, program: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn spawn(&self, program: &str, args: &[&str]) -> bool {
+        let child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match child {
+            Ok(mut child) => {
+                // Reap it on a plain thread rather than holding a blocking-pool one
+                std::thread::spawn(move || child.wait());
+                true
+            }
+            Err(error) => {
+                tracing::debug!(program, %error, "Could not start command");
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,382 @@
+use bollard::query_parameters::ListContainersOptions;
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{ALLOW, CONTENT_TYPE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt::Write as _;
+use std::net::{IpAddr, SocketAddr};
+use sysinfo::System;
+use tauri::async_runtime::JoinHandle;
+use tokio::net::TcpListener;
+
+use super::config::{self, PrometheusSettings};
+use super::cpu_sampler;
+use super::docker::{endpoint_status, get_connection_timeout, get_docker_client};
+use super::docker_endpoints;
+use super::docker_stats::{self, ContainerStats};
+use super::sys_info::{
+    get_gpu_info, get_storage_info, read_memory_info, storage_size_to_bytes, GpuInfo,
+};
+
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+const GB_IN_BYTES: f64 = 1024.0 * 1024.0 * 1024.0;
+
+// Metric name, help text and how to read the value for one per-device family
+type GpuFamily = (&'static str, &'static str, fn(&GpuInfo) -> Option<f64>);
+type ContainerFamily = (&'static str, &'static str, fn(&ContainerStats) -> f64);
+
+/// Builds an OpenMetrics text exposition one metric family at a time
+#[derive(Default)]
+struct OpenMetricsWriter {
+    out: String,
+}
+
+impl OpenMetricsWriter {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+                .collect();
+            let _ = write!(self.out, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(self.out, " {}", value);
+    }
+
+    /// A family with a single unlabeled sample
+    fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, "gauge", help);
+        self.sample(name, &[], value);
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push_str("# EOF\n");
+        self.out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Host metrics from `sys_info` and the CPU sampler. Blocking.
+fn write_host_metrics(writer: &mut OpenMetricsWriter) {
+    // Nothing is reported for the CPU until the sampler has taken a sample
+    if let Some(cpu) = cpu_sampler::latest_cpu_info() {
+        if let Some(usage) = cpu.usage_percent {
+            writer.gauge(
+                "desktop_agent_cpu_usage_percent",
+                "Host CPU usage over the latest sampling interval.",
+                usage as f64,
+            );
+        }
+        writer.gauge(
+            "desktop_agent_cpu_logical_cores",
+            "Number of logical CPU cores.",
+            cpu.logical_cores as f64,
+        );
+    }
+    writer.gauge(
+        "desktop_agent_uptime_seconds",
+        "Host uptime.",
+        System::uptime() as f64,
+    );
+
+    if let Ok(memory) = read_memory_info() {
+        writer.gauge(
+            "desktop_agent_memory_total_bytes",
+            "Total host memory.",
+            memory.total_gb * GB_IN_BYTES,
+        );
+        writer.gauge(
+            "desktop_agent_memory_used_bytes",
+            "Used host memory.",
+            memory.used_gb * GB_IN_BYTES,
+        );
+    }
+
+    if let Ok(storage) = get_storage_info() {
+        writer.family(
+            "desktop_agent_disk_total_bytes",
+            "gauge",
+            "Size of each mounted disk.",
+        );
+        for device in &storage.devices {
+            writer.sample(
+                "desktop_agent_disk_total_bytes",
+                &[("mount", &device.mount_point), ("name", &device.name)],
+                storage_size_to_bytes(device.total_size, &device.unit) as f64,
+            );
+        }
+        writer.family(
+            "desktop_agent_disk_available_bytes",
+            "gauge",
+            "Free space on each mounted disk.",
+        );
+        for device in &storage.devices {
+            writer.sample(
+                "desktop_agent_disk_available_bytes",
+                &[("mount", &device.mount_point), ("name", &device.name)],
+                storage_size_to_bytes(device.available_size, &device.unit) as f64,
+            );
+        }
+    }
+
+    if let Ok(gpus) = get_gpu_info() {
+        let gpus: Vec<(String, _)> = gpus
+            .into_iter()
+            .enumerate()
+            .map(|(index, gpu)| (index.to_string(), gpu))
+            .collect();
+        let families: [GpuFamily; 4] = [
+            (
+                "desktop_agent_gpu_utilization_percent",
+                "GPU utilization.",
+                |gpu| gpu.utilization_percent.map(f64::from),
+            ),
+            (
+                "desktop_agent_gpu_memory_used_bytes",
+                "Used GPU memory.",
+                |gpu| gpu.memory_used_gb.map(|gb| gb * GB_IN_BYTES),
+            ),
+            (
+                "desktop_agent_gpu_memory_total_bytes",
+                "Total GPU memory.",
+                |gpu| gpu.memory_total_gb.map(|gb| gb * GB_IN_BYTES),
+            ),
+            (
+                "desktop_agent_gpu_temperature_celsius",
+                "GPU temperature.",
+                |gpu| gpu.temperature_celsius.map(f64::from),
+            ),
+        ];
+        for (name, help, value) in families {
+            writer.family(name, "gauge", help);
+            for (index, gpu) in &gpus {
+                if let Some(value) = value(gpu) {
+                    writer.sample(name, &[("gpu", index), ("name", &gpu.name)], value);
+                }
+            }
+        }
+    }
+}
+
+/// Docker metrics for every endpoint, plus containers on the active one
+async fn write_docker_metrics(writer: &mut OpenMetricsWriter) {
+    let statuses: Vec<_> = docker_endpoints::all_states()
+        .iter()
+        .map(endpoint_status)
+        .collect();
+
+    writer.family(
+        "desktop_agent_docker_up",
+        "gauge",
+        "Whether the Docker daemon on the endpoint is reachable.",
+    );
+    for (endpoint, status) in &statuses {
+        writer.sample(
+            "desktop_agent_docker_up",
+            &[("endpoint", &endpoint.name)],
+            if status.is_running { 1.0 } else { 0.0 },
+        );
+    }
+
+    writer.family(
+        "desktop_agent_docker",
+        "info",
+        "Engine version and flavor of each connected endpoint.",
+    );
+    for (endpoint, status) in &statuses {
+        let Some(version) = &status.engine_version else {
+            continue;
+        };
+        let flavor = status
+            .engine_flavor
+            .and_then(|flavor| serde_json::to_value(flavor).ok())
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default();
+        writer.sample(
+            "desktop_agent_docker_info",
+            &[
+                ("endpoint", &endpoint.name),
+                ("version", &version.version),
+                ("api_version", &version.api_version),
+                ("flavor", &flavor),
+            ],
+            1.0,
+        );
+    }
+
+    // Container-level metrics follow the active endpoint, like the rest of the agent
+    let Ok(docker) = get_docker_client() else {
+        return;
+    };
+    let containers = tokio::time::timeout(
+        get_connection_timeout(),
+        docker.list_containers(Some(ListContainersOptions {
+            all: true,
+            ..Default::default()
+        })),
+    )
+    .await;
+    if let Ok(Ok(containers)) = containers {
+        let mut by_state: BTreeMap<String, usize> = BTreeMap::new();
+        for container in &containers {
+            let state = container
+                .state
+                .as_ref()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            *by_state.entry(state).or_default() += 1;
+        }
+        writer.family(
+            "desktop_agent_docker_containers",
+            "gauge",
+            "Containers on the active endpoint by state.",
+        );
+        for (state, count) in by_state {
+            writer.sample(
+                "desktop_agent_docker_containers",
+                &[("state", &state)],
+                count as f64,
+            );
+        }
+    }
+
+    let stats = docker_stats::latest_container_stats();
+    let families: [ContainerFamily; 3] = [
+        (
+            "desktop_agent_container_cpu_percent",
+            "Container CPU usage, as reported by docker stats.",
+            |s| s.cpu_percent,
+        ),
+        (
+            "desktop_agent_container_memory_usage_bytes",
+            "Container memory usage.",
+            |s| s.memory_usage_bytes as f64,
+        ),
+        (
+            "desktop_agent_container_memory_limit_bytes",
+            "Container memory limit.",
+            |s| s.memory_limit_bytes as f64,
+        ),
+    ];
+    for (name, help, value) in families {
+        writer.family(name, "gauge", help);
+        for container in &stats {
+            writer.sample(
+                name,
+                &[("id", &container.id), ("name", &container.name)],
+                value(container),
+            );
+        }
+    }
+}
+
+async fn render_metrics() -> String {
+    let host = tauri::async_runtime::spawn_blocking(|| {
+        let mut writer = OpenMetricsWriter::default();
+        write_host_metrics(&mut writer);
+        writer
+    })
+    .await;
+    let mut writer = match host {
+        Ok(writer) => writer,
+        Err(error) => {
+            tracing::error!(%error, "Collecting host metrics panicked");
+            OpenMetricsWriter::default()
+        }
+    };
+    write_docker_metrics(&mut writer).await;
+    writer.finish()
+}
+
+async fn handle_request(request: Request<Incoming>) -> Result<Response<Full<Bytes>>, Infallible> {
+    let response = match (request.method(), request.uri().path()) {
+        (&Method::GET, "/metrics") => Response::builder()
+            .header(CONTENT_TYPE, OPENMETRICS_CONTENT_TYPE)
+            .body(Full::new(Bytes::from(render_metrics().await))),
+        (_, "/metrics") => Response::builder()
+            .status(StatusCode::METHOD_NOT_ALLOWED)
+            .header(ALLOW, "GET")
+            .body(Full::new(Bytes::new())),
+        _ => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Full::new(Bytes::from_static(b"Not found\n"))),
+    };
+    Ok(response.expect("static response parts are valid"))
+}
+
+async fn serve(listener: TcpListener) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(error) => {
+                tracing::warn!(%error, "Could not accept metrics connection");
+                continue;
+            }
+        };
+        tauri::async_runtime::spawn(async move {
+            let connection = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service_fn(handle_request));
+            if let Err(error) = connection.await {
+                tracing::debug!(%error, "Metrics connection closed with an error");
+            }
+        });
+    }
+}
+
+async fn start_listener(settings: &PrometheusSettings) -> Option<JoinHandle<()>> {
+    let address: IpAddr = settings.bind_address.parse().ok()?;
+    let address = SocketAddr::new(address, settings.port);
+    match TcpListener::bind(address).await {
+        Ok(listener) => {
+            tracing::info!(%address, "Serving Prometheus metrics");
+            Some(tauri::async_runtime::spawn(serve(listener)))
+        }
+        Err(error) => {
+            tracing::warn!(%address, %error, "Could not start Prometheus listener");
+            None
+        }
+    }
+}
+
+/// Runs the `/metrics` listener while it is enabled in the config, restarting
+/// it whenever its address changes
+pub fn initialize_prometheus() {
+    tauri::async_runtime::spawn(async {
+        let mut config_updates = config::subscribe_to_config();
+        let mut running: Option<(PrometheusSettings, JoinHandle<()>)> = None;
+        loop {
+            let settings = config_updates.borrow_and_update().prometheus.clone();
+            if running.as_ref().map(|(current, _)| current) != Some(&settings) {
+                if let Some((_, listener)) = running.take() {
+                    listener.abort();
+                    tracing::info!("Stopped Prometheus listener");
+                }
+                if settings.enabled {
+                    running = start_listener(&settings)
+                        .await
+                        .map(|listener| (settings, listener));
+                }
+            }
+            if config_updates.changed().await.is_err() {
+                break;
+            }
+        }
+    });
+}
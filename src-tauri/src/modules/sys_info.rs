@@ -4,6 +4,9 @@ use sysinfo::{Disks, System};
 use thiserror::Error;
 use std::env;
 
+use super::cpu_sampler;
+use super::platform::{CommandRunner, Platform, SystemCommandRunner};
+
 /// Constants for memory calculations
 const GB_IN_BYTES: f64 = 1024.0 * 1024.0 * 1024.0;
 
@@ -30,9 +33,42 @@ pub struct OperatingSystemInfo {
 #[derive(Serialize, Clone)]
 pub struct CpuInfo {
     pub brand: String,
+    pub vendor: String,
+    /// Average current frequency across logical cores in MHz
     pub frequency: u64,
     pub physical_cores: usize,
     pub logical_cores: usize,
+    /// Overall usage; `None` until the CPU sampler has measured it
+    pub usage_percent: Option<f32>,
+    pub cores: Vec<CpuCoreInfo>,
+    /// Not available on Windows
+    pub load_average: Option<LoadAverage>,
+    pub caches: Vec<CpuCache>,
+}
+
+/// Usage and current frequency of one logical core
+#[derive(Serialize, Clone)]
+pub struct CpuCoreInfo {
+    pub name: String,
+    pub usage_percent: Option<f32>,
+    pub frequency: u64,
+}
+
+/// 1, 5 and 15 minute load averages
+#[derive(Serialize, Clone)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+/// One CPU cache as seen by the first core
+#[derive(Serialize, Clone)]
+pub struct CpuCache {
+    pub level: u32,
+    /// "data", "instruction" or "unified"
+    pub kind: String,
+    pub size_kb: u64,
 }
 
 /// GPU information
@@ -60,13 +96,14 @@ pub struct StorageInfo {
 }
 
 /// Individual storage device information
-#[derive(Serialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct StorageDevice {
     pub name: String,
     pub total_size: f64,
     pub used_size: f64,
     pub available_size: f64,
     pub unit: String, // "GB" or "TB"
+    pub mount_point: String,
 }
 
 /// Complete system information
@@ -94,6 +131,12 @@ fn format_storage_size(bytes: u64) -> (f64, &'static str) {
     }
 }
 
+/// Converts a size reported by `format_storage_size` back to bytes
+pub(crate) fn storage_size_to_bytes(size: f64, unit: &str) -> u64 {
+    let gb = if unit == "TB" { size * 1024.0 } else { size };
+    (gb * GB_IN_BYTES) as u64
+}
+
 /// Formats uptime from seconds to a human-readable string
 fn format_uptime(seconds: u64) -> String {
     let days = seconds / 86400;
@@ -177,11 +220,14 @@ fn get_os_info() -> Result<OperatingSystemInfo, SysInfoError> {
     let uptime = System::uptime();
     let hostname = get_hostname()?;
     
-    println!("[DEBUG] OS Name: {}", name);
-    println!("[DEBUG] OS Version: {}", version);
-    println!("[DEBUG] Kernel Version: {}", kernel_version);
-    println!("[DEBUG] Hostname: {}", hostname);
-    println!("[DEBUG] Uptime: {}", format_uptime(uptime));
+    tracing::debug!(
+        %name,
+        %version,
+        %kernel_version,
+        %hostname,
+        uptime = %format_uptime(uptime),
+        "Collected OS info"
+    );
     
     Ok(OperatingSystemInfo {
         name,
@@ -192,41 +238,160 @@ fn get_os_info() -> Result<OperatingSystemInfo, SysInfoError> {
     })
 }
 
-/// Collect CPU information
-fn get_cpu_info(sys: &System) -> Result<CpuInfo, SysInfoError> {
+/// Parses a sysfs cache size such as "32K" or "16M" into kilobytes
+fn parse_cache_size_kb(size: &str) -> Option<u64> {
+    let size = size.trim();
+    if let Some(kb) = size.strip_suffix('K') {
+        kb.parse().ok()
+    } else if let Some(mb) = size.strip_suffix('M') {
+        mb.parse::<u64>().ok().map(|mb| mb * 1024)
+    } else {
+        size.parse::<u64>().ok().map(|bytes| bytes / 1024)
+    }
+}
+
+/// Detect CPU caches from sysfs, sysctl or WMI. Blocking.
+fn detect_cpu_caches(platform: Platform, runner: &dyn CommandRunner) -> Vec<CpuCache> {
+    let mut caches = Vec::new();
+    match platform {
+        Platform::Linux => {
+            let Ok(entries) = std::fs::read_dir("/sys/devices/system/cpu/cpu0/cache") else {
+                return caches;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let read = |name: &str| std::fs::read_to_string(path.join(name)).ok();
+                let (Some(level), Some(kind), Some(size)) =
+                    (read("level"), read("type"), read("size"))
+                else {
+                    continue;
+                };
+                if let (Ok(level), Some(size_kb)) =
+                    (level.trim().parse(), parse_cache_size_kb(&size))
+                {
+                    caches.push(CpuCache {
+                        level,
+                        kind: kind.trim().to_lowercase(),
+                        size_kb,
+                    });
+                }
+            }
+        }
+        Platform::MacOs => {
+            for (key, level, kind) in [
+                ("hw.l1dcachesize", 1, "data"),
+                ("hw.l1icachesize", 1, "instruction"),
+                ("hw.l2cachesize", 2, "unified"),
+                ("hw.l3cachesize", 3, "unified"),
+            ] {
+                let bytes = runner
+                    .run("sysctl", &["-n", key])
+                    .and_then(|output| output.trim().parse::<u64>().ok());
+                if let Some(bytes) = bytes.filter(|bytes| *bytes > 0) {
+                    caches.push(CpuCache {
+                        level,
+                        kind: kind.to_string(),
+                        size_kb: bytes / 1024,
+                    });
+                }
+            }
+        }
+        Platform::Windows => {
+            // Win32_Processor reports sizes in KB
+            let output = runner.run(
+                "powershell",
+                &[
+                    "-NoProfile",
+                    "-NonInteractive",
+                    "-Command",
+                    "$p = Get-CimInstance Win32_Processor | Select-Object -First 1; \"$($p.L2CacheSize) $($p.L3CacheSize)\"",
+                ],
+            );
+            if let Some(output) = output {
+                for (level, size) in (2..).zip(output.split_whitespace()) {
+                    if let Some(size_kb) = size.parse::<u64>().ok().filter(|size| *size > 0) {
+                        caches.push(CpuCache {
+                            level,
+                            kind: "unified".to_string(),
+                            size_kb,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    caches.sort_by(|a, b| a.level.cmp(&b.level).then_with(|| a.kind.cmp(&b.kind)));
+    caches
+}
+
+static CPU_CACHES: std::sync::OnceLock<Vec<CpuCache>> = std::sync::OnceLock::new();
+
+/// Collect CPU information. Usage is only meaningful when `sys` has refreshed
+/// its CPUs at least twice, so `usage_measured` says whether to report it.
+pub(crate) fn get_cpu_info(sys: &System, usage_measured: bool) -> Result<CpuInfo, SysInfoError> {
     let cpus = sys.cpus();
     if cpus.is_empty() {
-        println!("[DEBUG] No CPUs found");
+        tracing::warn!("No CPUs found");
         return Err(SysInfoError::System("No CPUs found".to_string()));
     }
     
     let cpu = &cpus[0];
     let brand = cpu.brand().to_string();
-    let frequency = cpu.frequency();
+    let vendor = cpu.vendor_id().to_string();
+    let frequency = cpus.iter().map(|cpu| cpu.frequency()).sum::<u64>() / cpus.len() as u64;
     let physical_cores = System::physical_core_count().unwrap_or(0);
     let logical_cores = cpus.len();
+    let usage_percent = usage_measured.then(|| sys.global_cpu_usage());
+    let cores = cpus
+        .iter()
+        .map(|cpu| CpuCoreInfo {
+            name: cpu.name().to_string(),
+            usage_percent: usage_measured.then(|| cpu.cpu_usage()),
+            frequency: cpu.frequency(),
+        })
+        .collect();
+    let load_average = if cfg!(target_os = "windows") {
+        None
+    } else {
+        let load = System::load_average();
+        Some(LoadAverage {
+            one: load.one,
+            five: load.five,
+            fifteen: load.fifteen,
+        })
+    };
+    let caches = CPU_CACHES
+        .get_or_init(|| detect_cpu_caches(Platform::current(), &SystemCommandRunner))
+        .clone();
     
-    println!("[DEBUG] CPU Brand: {}", brand);
-    println!("[DEBUG] CPU Frequency: {} MHz", frequency);
-    println!("[DEBUG] Physical Cores: {}", physical_cores);
-    println!("[DEBUG] Logical Cores: {}", logical_cores);
+    tracing::trace!(
+        %brand,
+        frequency_mhz = frequency,
+        physical_cores,
+        logical_cores,
+        usage_percent,
+        "Collected CPU info"
+    );
     
     Ok(CpuInfo {
         brand,
+        vendor,
         frequency,
         physical_cores,
         logical_cores,
+        usage_percent,
+        cores,
+        load_average,
+        caches,
     })
 }
 
 /// Collect memory information
-fn get_memory_info(sys: &System) -> Result<MemoryInfo, SysInfoError> {
+pub(crate) fn get_memory_info(sys: &System) -> Result<MemoryInfo, SysInfoError> {
     let total_gb = bytes_to_gb(sys.total_memory());
     let used_gb = bytes_to_gb(sys.used_memory());
     let free_gb = bytes_to_gb(sys.free_memory());
-    println!("[DEBUG] Memory Total: {:.2} GB", total_gb);
-    println!("[DEBUG] Memory Used: {:.2} GB", used_gb);
-    println!("[DEBUG] Memory Free: {:.2} GB", free_gb);
+    tracing::debug!(total_gb, used_gb, free_gb, "Collected memory info");
     Ok(MemoryInfo {
         total_gb,
         used_gb,
@@ -234,10 +399,17 @@ fn get_memory_info(sys: &System) -> Result<MemoryInfo, SysInfoError> {
     })
 }
 
+/// Collect memory information from a fresh memory-only snapshot
+pub(crate) fn read_memory_info() -> Result<MemoryInfo, SysInfoError> {
+    let mut sys = System::new();
+    sys.refresh_memory();
+    get_memory_info(&sys)
+}
+
 /// Collect storage information
-fn get_storage_info() -> Result<StorageInfo, SysInfoError> {
+pub(crate) fn get_storage_info() -> Result<StorageInfo, SysInfoError> {
     let disks = Disks::new_with_refreshed_list();
-    println!("[DEBUG] Disks found: {}", disks.list().len());
+    tracing::debug!(count = disks.list().len(), "Collected disk list");
     let devices = disks
         .list()
         .iter()
@@ -250,16 +422,18 @@ fn get_storage_info() -> Result<StorageInfo, SysInfoError> {
             let raw_name = disk.name().to_string_lossy().to_string();
             let mount_point = disk.mount_point().to_string_lossy().to_string();
             
-            // Clone for debug printing
-            let raw_name_debug = raw_name.clone();
-            let mount_point_debug = mount_point.clone();
-            
             // Create a better display name
             let display_name = create_display_name(&raw_name, &mount_point);
             
-            println!(
-                "[DEBUG] Disk: Raw='{}' | Mount='{}' | Display='{}' | Total: {:.2} {} | Used: {:.2} {} | Available: {:.2} {}",
-                raw_name_debug, mount_point_debug, display_name, total, unit, used, unit, available, unit
+            tracing::trace!(
+                raw_name = %raw_name,
+                mount_point = %mount_point,
+                display_name = %display_name,
+                total,
+                used,
+                available,
+                unit,
+                "Disk"
             );
             
             StorageDevice {
@@ -268,6 +442,7 @@ fn get_storage_info() -> Result<StorageInfo, SysInfoError> {
                 used_size: used,
                 available_size: available,
                 unit: unit.to_string(),
+                mount_point,
             }
         })
         .collect();
@@ -275,12 +450,12 @@ fn get_storage_info() -> Result<StorageInfo, SysInfoError> {
 }
 
 /// Collect GPU information using NVIDIA Management Library
-fn get_gpu_info() -> Result<Vec<GpuInfo>, SysInfoError> {
+pub(crate) fn get_gpu_info() -> Result<Vec<GpuInfo>, SysInfoError> {
     let nvml = Nvml::init().map_err(|e| SysInfoError::Nvml(e.to_string()))?;
     let count = nvml
         .device_count()
         .map_err(|e| SysInfoError::Nvml(e.to_string()))?;
-    println!("[DEBUG] NVML GPU count: {}", count);
+    tracing::debug!(count, "NVML GPU count");
     let mut gpus = Vec::new();
     for i in 0..count {
         let device = nvml
@@ -293,9 +468,13 @@ fn get_gpu_info() -> Result<Vec<GpuInfo>, SysInfoError> {
         let temperature = device
             .temperature(nvml_wrapper::enum_wrappers::device::TemperatureSensor::Gpu)
             .ok();
-        println!(
-            "[DEBUG] GPU {}: {} | Mem: {:?} | Util: {:?} | Temp: {:?}",
-            i, name, memory, utilization, temperature
+        tracing::trace!(
+            index = i,
+            %name,
+            memory = ?memory,
+            utilization = ?utilization,
+            temperature = ?temperature,
+            "GPU"
         );
         gpus.push(GpuInfo {
             name,
@@ -311,11 +490,15 @@ fn get_gpu_info() -> Result<Vec<GpuInfo>, SysInfoError> {
 /// Tauri command to get complete system information
 #[tauri::command]
 pub async fn get_system_info() -> SystemInfo {
+    let _span = tracing::debug_span!("get_system_info").entered();
     let mut sys = System::new_all();
     sys.refresh_all();
     SystemInfo {
         os: get_os_info(),
-        cpu: get_cpu_info(&sys),
+        // A fresh snapshot cannot measure usage, so prefer the sampler's
+        cpu: cpu_sampler::latest_cpu_info()
+            .map(Ok)
+            .unwrap_or_else(|| get_cpu_info(&sys, false)),
         gpu: get_gpu_info(),
         memory: get_memory_info(&sys),
         storage: get_storage_info(),
@@ -0,0 +1,326 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::image::Image;
+use tauri::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{Manager, Wry};
+
+use super::alerts;
+use super::config;
+use super::cpu_sampler;
+use super::docker::{self, endpoint_status, get_docker_state};
+use super::docker_containers::restart_docker_container;
+use super::sys_info::read_memory_info;
+
+const TRAY_ID: &str = "main";
+const MAIN_WINDOW: &str = "main";
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+const MENU_SHOW: &str = "show";
+const MENU_REFRESH: &str = "refresh";
+const MENU_RESTART_PINNED: &str = "restart-pinned";
+const MENU_PAUSE: &str = "pause";
+const MENU_QUIT: &str = "quit";
+
+// Only hide on close when the tray exists, otherwise the window could not be brought back
+static TRAY_READY: AtomicBool = AtomicBool::new(false);
+
+/// Overall state shown by the tray icon's badge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrayState {
+    Running,
+    Stopped,
+    Alerting,
+    Paused,
+}
+
+impl TrayState {
+    fn badge_color(self) -> [u8; 3] {
+        match self {
+            TrayState::Running => [46, 160, 67],
+            TrayState::Stopped => [128, 128, 128],
+            TrayState::Alerting => [218, 54, 51],
+            TrayState::Paused => [219, 171, 9],
+        }
+    }
+}
+
+/// Menu items whose text or state follows the agent's status
+struct TrayMenu {
+    docker: MenuItem<Wry>,
+    usage: MenuItem<Wry>,
+    alerts: MenuItem<Wry>,
+    restart_pinned: MenuItem<Wry>,
+    pause: CheckMenuItem<Wry>,
+}
+
+/// The app icon with a colored dot in the bottom-right corner
+fn badged_icon(base: &Image<'_>, color: [u8; 3]) -> Image<'static> {
+    let (width, height) = (base.width(), base.height());
+    let mut rgba = base.rgba().to_vec();
+    let radius = width.min(height) as f64 / 4.0;
+    let outline = (radius / 6.0).max(1.0);
+    let center_x = width as f64 - radius - outline;
+    let center_y = height as f64 - radius - outline;
+
+    for y in 0..height {
+        for x in 0..width {
+            let distance =
+                ((x as f64 + 0.5 - center_x).powi(2) + (y as f64 + 0.5 - center_y).powi(2)).sqrt();
+            let pixel = match distance {
+                d if d <= radius => [color[0], color[1], color[2], 255],
+                d if d <= radius + outline => [255, 255, 255, 255],
+                _ => continue,
+            };
+            let offset = ((y * width + x) * 4) as usize;
+            rgba[offset..offset + 4].copy_from_slice(&pixel);
+        }
+    }
+    Image::new_owned(rgba, width, height)
+}
+
+fn show_main_window(app_handle: &tauri::AppHandle) {
+    let Some(window) = app_handle.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+    if let Err(error) = window
+        .show()
+        .and_then(|_| window.unminimize())
+        .and_then(|_| window.set_focus())
+    {
+        tracing::warn!(%error, "Could not show the main window");
+    }
+}
+
+fn handle_menu_event(app_handle: &tauri::AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        MENU_SHOW => show_main_window(app_handle),
+        MENU_REFRESH => {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                docker::refresh_active_endpoint(&app_handle).await;
+            });
+        }
+        MENU_RESTART_PINNED => {
+            let Some(container) = config::current_config().tray.pinned_container else {
+                return;
+            };
+            tauri::async_runtime::spawn(async move {
+                match restart_docker_container(container.clone(), None).await {
+                    Ok(_) => tracing::info!(%container, "Restarted pinned container"),
+                    Err(error) => {
+                        tracing::warn!(%container, %error, "Could not restart pinned container")
+                    }
+                }
+            });
+        }
+        // The check mark toggles itself; the update loop keeps it in sync afterwards
+        MENU_PAUSE => docker::set_monitoring_paused(!docker::is_monitoring_paused()),
+        MENU_QUIT => app_handle.exit(0),
+        _ => {}
+    }
+}
+
+fn handle_tray_event(tray: &TrayIcon, event: TrayIconEvent) {
+    if let TrayIconEvent::Click {
+        button: MouseButton::Left,
+        button_state: MouseButtonState::Up,
+        ..
+    } = event
+    {
+        show_main_window(tray.app_handle());
+    }
+}
+
+/// CPU and memory usage for the menu, e.g. "CPU 12% · Memory 6.2 / 16.0 GB".
+/// CPU usage is the CPU sampler's latest reading.
+fn usage_headline() -> String {
+    let cpu = cpu_sampler::latest_cpu_info().and_then(|cpu| cpu.usage_percent);
+    match (cpu, read_memory_info()) {
+        (Some(cpu), Ok(memory)) => format!(
+            "CPU {:.0}% · Memory {:.1} / {:.1} GB",
+            cpu, memory.used_gb, memory.total_gb
+        ),
+        (Some(cpu), Err(_)) => format!("CPU {:.0}%", cpu),
+        (None, Ok(memory)) => format!(
+            "CPU measuring… · Memory {:.1} / {:.1} GB",
+            memory.used_gb, memory.total_gb
+        ),
+        (None, Err(_)) => "CPU and memory: measuring…".to_string(),
+    }
+}
+
+/// Applies the current Docker, alert and pause state to the tray
+fn update_tray(
+    tray: &TrayIcon,
+    menu: &TrayMenu,
+    base_icon: Option<&Image<'_>>,
+    last_state: &mut Option<TrayState>,
+    usage: &str,
+) -> tauri::Result<()> {
+    let (endpoint, status) = endpoint_status(&get_docker_state());
+    let active_alerts = alerts::active_alerts().len();
+    let paused = docker::is_monitoring_paused();
+
+    let docker_text = if status.is_running {
+        format!("Docker running on {}", endpoint.name)
+    } else {
+        format!("Docker stopped on {}", endpoint.name)
+    };
+    let alerts_text = match active_alerts {
+        0 => "No active alerts".to_string(),
+        1 => "1 active alert".to_string(),
+        count => format!("{} active alerts", count),
+    };
+
+    menu.docker.set_text(&docker_text)?;
+    menu.usage.set_text(usage)?;
+    menu.alerts.set_text(&alerts_text)?;
+    menu.pause.set_checked(paused)?;
+    match config::current_config().tray.pinned_container {
+        Some(container) => {
+            menu.restart_pinned
+                .set_text(format!("Restart {}", container))?;
+            menu.restart_pinned.set_enabled(status.is_running)?;
+        }
+        None => {
+            menu.restart_pinned.set_text("Restart pinned container")?;
+            menu.restart_pinned.set_enabled(false)?;
+        }
+    }
+
+    let mut tooltip = format!(
+        "Desktop Agent: {}, {}",
+        docker_text,
+        alerts_text.to_lowercase()
+    );
+    if paused {
+        tooltip.push_str(" (monitoring paused)");
+    }
+    tray.set_tooltip(Some(tooltip))?;
+
+    let state = if paused {
+        TrayState::Paused
+    } else if active_alerts > 0 {
+        TrayState::Alerting
+    } else if status.is_running {
+        TrayState::Running
+    } else {
+        TrayState::Stopped
+    };
+    if *last_state != Some(state) {
+        if let Some(base) = base_icon {
+            tray.set_icon(Some(badged_icon(base, state.badge_color())))?;
+        }
+        *last_state = Some(state);
+    }
+    Ok(())
+}
+
+// Refresh the tray periodically and whenever the configuration changes
+async fn tray_update_loop(app_handle: tauri::AppHandle, tray: TrayIcon, menu: TrayMenu) {
+    let base_icon = app_handle.default_window_icon().cloned();
+    let mut last_state = None;
+    let mut config_updates = config::subscribe_to_config();
+    let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+    let mut usage = "CPU and memory: measuring…".to_string();
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => usage = usage_headline(),
+            Ok(()) = config_updates.changed() => {}
+        }
+
+        if let Err(error) = update_tray(&tray, &menu, base_icon.as_ref(), &mut last_state, &usage) {
+            tracing::warn!(%error, "Could not update the tray");
+        }
+    }
+}
+
+fn build_tray(app_handle: &tauri::AppHandle) -> tauri::Result<(TrayIcon, TrayMenu)> {
+    let menu = TrayMenu {
+        docker: MenuItem::new(app_handle, "Docker: checking…", false, None::<&str>)?,
+        usage: MenuItem::new(
+            app_handle,
+            "CPU and memory: measuring…",
+            false,
+            None::<&str>,
+        )?,
+        alerts: MenuItem::new(app_handle, "No active alerts", false, None::<&str>)?,
+        restart_pinned: MenuItem::with_id(
+            app_handle,
+            MENU_RESTART_PINNED,
+            "Restart pinned container",
+            false,
+            None::<&str>,
+        )?,
+        pause: CheckMenuItem::with_id(
+            app_handle,
+            MENU_PAUSE,
+            "Pause monitoring",
+            true,
+            docker::is_monitoring_paused(),
+            None::<&str>,
+        )?,
+    };
+    let show = MenuItem::with_id(app_handle, MENU_SHOW, "Show window", true, None::<&str>)?;
+    let refresh = MenuItem::with_id(app_handle, MENU_REFRESH, "Refresh", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app_handle, MENU_QUIT, "Quit", true, None::<&str>)?;
+
+    let tray_menu = Menu::with_items(
+        app_handle,
+        &[
+            &menu.docker,
+            &menu.usage,
+            &menu.alerts,
+            &PredefinedMenuItem::separator(app_handle)?,
+            &show,
+            &refresh,
+            &menu.restart_pinned,
+            &menu.pause,
+            &PredefinedMenuItem::separator(app_handle)?,
+            &quit,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Desktop Agent")
+        .menu(&tray_menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(handle_menu_event)
+        .on_tray_icon_event(handle_tray_event);
+    if let Some(icon) = app_handle.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    let tray = builder.build(app_handle)?;
+    Ok((tray, menu))
+}
+
+/// Creates the tray icon and keeps it in sync with Docker, alerts and usage
+pub fn initialize_tray(app_handle: &tauri::AppHandle) {
+    match build_tray(app_handle) {
+        Ok((tray, menu)) => {
+            TRAY_READY.store(true, Ordering::Relaxed);
+            tauri::async_runtime::spawn(tray_update_loop(app_handle.clone(), tray, menu));
+        }
+        // e.g. Linux desktops without an appindicator implementation
+        Err(error) => tracing::warn!(%error, "Could not create the tray icon"),
+    }
+}
+
+/// Hides the main window instead of closing it when close-to-tray is enabled,
+/// so monitoring continues in the background
+pub fn handle_window_event(window: &tauri::Window, event: &tauri::WindowEvent) {
+    if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+        if window.label() != MAIN_WINDOW
+            || !TRAY_READY.load(Ordering::Relaxed)
+            || !config::current_config().tray.close_to_tray
+        {
+            return;
+        }
+        api.prevent_close();
+        if let Err(error) = window.hide() {
+            tracing::warn!(%error, "Could not hide the main window");
+        }
+    }
+}
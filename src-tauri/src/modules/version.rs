@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Labels that mark a pre-release rather than an image variant such as `dind`
+const PRE_RELEASE_LABELS: &[&str] = &["alpha", "beta", "rc", "tp", "dev", "pre", "preview"];
+
+/// Release channel to follow when choosing the latest version
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    /// Final releases only
+    #[default]
+    Stable,
+    /// Final releases and pre-releases (alpha, beta, rc)
+    Test,
+}
+
+/// One dot-separated part of a pre-release, e.g. `rc` and `1` in `rc.1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Identifier {
+    Numeric(u64),
+    Alpha(String),
+}
+
+impl Ord for Identifier {
+    // Numeric identifiers sort before alphanumeric ones, as in semver
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Identifier::Numeric(a), Identifier::Numeric(b)) => a.cmp(b),
+            (Identifier::Numeric(_), Identifier::Alpha(_)) => Ordering::Less,
+            (Identifier::Alpha(_), Identifier::Numeric(_)) => Ordering::Greater,
+            (Identifier::Alpha(a), Identifier::Alpha(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// What follows the numeric part of a version string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Suffix {
+    None,
+    /// `-rc.1`, `-beta.2`: sorts before the final release
+    PreRelease(Vec<Identifier>),
+    /// Distribution package revision such as `-1ubuntu1~22.04` or `-1.el9`,
+    /// which ships the same upstream release
+    Distro(String),
+    /// Image tag variants such as `-dind` or `-cli`, which are not versions of their own
+    Variant(String),
+}
+
+/// A parsed Docker version: `major.minor.patch`, an optional suffix and
+/// ignored build metadata (`+...` or a fourth Windows build component).
+/// Versions are equal when they name the same upstream release.
+#[derive(Debug, Clone)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub suffix: Suffix,
+    /// Whether all three numeric components were present, so `28` and `28.0`
+    /// tag aliases can be told apart from releases
+    pub complete: bool,
+}
+
+impl Version {
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim().trim_matches('"');
+        let input = input.strip_prefix("docker-").unwrap_or(input);
+        let input = input
+            .strip_prefix('v')
+            .or_else(|| input.strip_prefix('V'))
+            .unwrap_or(input);
+        // Package epochs ("5:24.0.7-1~ubuntu") do not change the upstream version
+        let input = match input.split_once(':') {
+            Some((epoch, rest)) if epoch.chars().all(|c| c.is_ascii_digit()) => rest,
+            _ => input,
+        };
+        let input = input.split_once('+').map_or(input, |(version, _)| version);
+
+        let (numbers, suffix) = match input.find(['-', '~']) {
+            Some(index) => (&input[..index], Some(&input[index + 1..])),
+            None => (input, None),
+        };
+
+        let mut components = numbers.split('.');
+        let major = components.next()?.parse().ok()?;
+        let minor = components.next().map(str::parse).transpose().ok()?;
+        let patch = components.next().map(str::parse).transpose().ok()?;
+        // A fourth component is a Windows build number
+        if components.any(|c| c.parse::<u64>().is_err()) {
+            return None;
+        }
+
+        Some(Self {
+            major,
+            minor: minor.unwrap_or(0),
+            patch: patch.unwrap_or(0),
+            suffix: suffix.map_or(Suffix::None, parse_suffix),
+            complete: minor.is_some() && patch.is_some(),
+        })
+    }
+
+    /// Whether this names a release on `channel`, as opposed to a tag alias or image variant
+    pub fn is_on_channel(&self, channel: UpdateChannel) -> bool {
+        if !self.complete {
+            return false;
+        }
+        match &self.suffix {
+            Suffix::None | Suffix::Distro(_) => true,
+            Suffix::PreRelease(_) => channel == UpdateChannel::Test,
+            Suffix::Variant(_) => false,
+        }
+    }
+}
+
+fn parse_suffix(suffix: &str) -> Suffix {
+    if suffix.is_empty() {
+        return Suffix::None;
+    }
+    // Package revisions start with a number: "1ubuntu1", "0.el9", "1~ubuntu.22.04"
+    if suffix.starts_with(|c: char| c.is_ascii_digit()) {
+        return Suffix::Distro(suffix.to_string());
+    }
+
+    let label: String = suffix
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_lowercase();
+    if !PRE_RELEASE_LABELS.contains(&label.as_str()) {
+        return Suffix::Variant(suffix.to_string());
+    }
+
+    // Split "rc.1" and "rc1" alike into ["rc", 1]
+    let mut identifiers = Vec::new();
+    for part in suffix.split('.') {
+        let alpha: String = part.chars().take_while(|c| !c.is_ascii_digit()).collect();
+        let digits = &part[alpha.len()..];
+        if !alpha.is_empty() {
+            identifiers.push(Identifier::Alpha(alpha.to_lowercase()));
+        }
+        if !digits.is_empty() {
+            identifiers.push(match digits.parse() {
+                Ok(n) => Identifier::Numeric(n),
+                Err(_) => Identifier::Alpha(digits.to_string()),
+            });
+        }
+    }
+    Suffix::PreRelease(identifiers)
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.suffix, &other.suffix) {
+                (Suffix::PreRelease(a), Suffix::PreRelease(b)) => a.cmp(b),
+                (Suffix::PreRelease(_), _) => Ordering::Less,
+                (_, Suffix::PreRelease(_)) => Ordering::Greater,
+                // Distro revisions and variants ship the same upstream release
+                _ => Ordering::Equal,
+            })
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Whether `latest` is a newer release than `current`. Unparseable versions
+/// never count as newer.
+pub fn is_newer(current: &str, latest: &str) -> bool {
+    match (Version::parse(current), Version::parse(latest)) {
+        (Some(current), Some(latest)) => latest > current,
+        _ => false,
+    }
+}
+
+/// Picks the highest version on `channel` from `candidates`, returning its index
+pub fn latest_on_channel<'a>(
+    candidates: impl IntoIterator<Item = &'a str>,
+    channel: UpdateChannel,
+) -> Option<usize> {
+    candidates
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, candidate)| Some((index, Version::parse(candidate)?)))
+        .filter(|(_, version)| version.is_on_channel(channel))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pre_release_sorts_before_release() {
+        assert!(is_newer("25.0.0-rc.1", "25.0.0"));
+        assert!(!is_newer("25.0.0", "25.0.0-rc.1"));
+        assert!(is_newer("25.0.0-rc.1", "25.0.0-rc.2"));
+        assert!(is_newer("25.0.0-beta.3", "25.0.0-rc.1"));
+        assert!(Version::parse("25.0.0-rc1") == Version::parse("25.0.0-rc.1"));
+    }
+
+    #[test]
+    fn distro_revision_is_the_same_release() {
+        let distro = Version::parse("24.0.7-1ubuntu").unwrap();
+        assert_eq!(distro.suffix, Suffix::Distro("1ubuntu".to_string()));
+        assert_eq!(distro, Version::parse("24.0.7").unwrap());
+        assert_eq!(
+            Version::parse("5:24.0.7-1~ubuntu.22.04~jammy"),
+            Version::parse("24.0.7")
+        );
+        assert!(!is_newer("24.0.7", "24.0.7-1ubuntu"));
+        assert!(is_newer("24.0.7-1ubuntu", "24.0.8"));
+        assert!(distro.is_on_channel(UpdateChannel::Stable));
+    }
+
+    #[test]
+    fn image_variants_are_not_releases() {
+        for tag in ["27.3.1-dind", "27.3.1-cli", "27-dind", "dind"] {
+            let on_channel =
+                Version::parse(tag).is_some_and(|v| v.is_on_channel(UpdateChannel::Test));
+            assert!(!on_channel, "{} should not be a release", tag);
+        }
+        let tags = ["27.3.1", "27.3.2-dind", "27.3.2-cli", "27", "27.3"];
+        assert_eq!(latest_on_channel(tags, UpdateChannel::Stable), Some(0));
+    }
+
+    #[test]
+    fn channel_controls_pre_releases() {
+        let tags = ["26.1.4", "27.0.0-rc.2", "26.1.3"];
+        assert_eq!(latest_on_channel(tags, UpdateChannel::Stable), Some(0));
+        assert_eq!(latest_on_channel(tags, UpdateChannel::Test), Some(1));
+    }
+
+    #[test]
+    fn parses_prefixes_and_build_metadata() {
+        let expected = Version::parse("24.0.7").unwrap();
+        for input in [
+            "v24.0.7",
+            "docker-v24.0.7",
+            "24.0.7+azure-1",
+            "\"24.0.7\"",
+            "24.0.7.1",
+        ] {
+            assert_eq!(Version::parse(input).as_ref(), Some(&expected), "{}", input);
+        }
+        assert!(Version::parse("latest").is_none());
+        assert!(!is_newer("24.0.7", "not-a-version"));
+    }
+}
@@ -0,0 +1,516 @@
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sysinfo::System;
+use thiserror::Error;
+
+use super::alerts::AlertSeverity;
+use super::config::{self, WebhookSettings};
+use super::docker;
+use super::notifications::NotificationCategory;
+
+/// Unix timestamp in seconds at which the payload was signed
+const TIMESTAMP_HEADER: &str = "X-Desktop-Agent-Timestamp";
+/// `sha256=` followed by the hex HMAC-SHA256 of `<timestamp>.<body>`
+const SIGNATURE_HEADER: &str = "X-Desktop-Agent-Signature";
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// Number of deliveries kept for `get_webhook_deliveries`
+const MAX_DELIVERIES: usize = 100;
+
+#[derive(Error, Debug, Clone, Serialize)]
+pub enum WebhookError {
+    #[error("No webhook named {0}")]
+    NotFound(String),
+}
+
+/// Body layout expected by the receiving service
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// The event as a flat JSON object
+    #[default]
+    Generic,
+    /// Slack incoming webhook message
+    Slack,
+    /// Microsoft Teams connector card
+    Teams,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// A webhook from the agent config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookTarget {
+    pub name: String,
+    pub url: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// Categories sent to this webhook; all of them when empty
+    #[serde(default)]
+    pub categories: Vec<NotificationCategory>,
+    /// JSON body replacing the format's own, with `{{title}}`, `{{message}}`,
+    /// `{{severity}}`, `{{category}}`, `{{timestamp}}` and `{{host}}` placeholders
+    /// inside its strings
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Key used to sign each payload
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// An alert or Docker transition as sent to webhooks
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub category: NotificationCategory,
+    pub severity: AlertSeverity,
+    pub title: String,
+    pub message: String,
+    pub timestamp: String,
+}
+
+impl WebhookEvent {
+    pub(crate) fn new(
+        category: NotificationCategory,
+        severity: AlertSeverity,
+        title: &str,
+        message: &str,
+    ) -> Self {
+        Self {
+            category,
+            severity,
+            title: title.to_string(),
+            message: message.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Outcome of sending one event to one webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookDelivery {
+    pub webhook: String,
+    pub title: String,
+    pub success: bool,
+    /// HTTP status of the last attempt, if the server answered
+    pub status: Option<u16>,
+    pub attempts: u32,
+    pub error: Option<String>,
+    pub timestamp: String,
+}
+
+static DELIVERIES: std::sync::OnceLock<Arc<Mutex<VecDeque<WebhookDelivery>>>> =
+    std::sync::OnceLock::new();
+
+fn get_deliveries() -> Arc<Mutex<VecDeque<WebhookDelivery>>> {
+    DELIVERIES
+        .get_or_init(|| Arc::new(Mutex::new(VecDeque::new())))
+        .clone()
+}
+
+fn record_delivery(delivery: WebhookDelivery) {
+    let deliveries = get_deliveries();
+    let mut deliveries_guard = deliveries.lock().unwrap();
+    if deliveries_guard.len() >= MAX_DELIVERIES {
+        deliveries_guard.pop_front();
+    }
+    deliveries_guard.push_back(delivery);
+}
+
+// Serialized name of a unit enum variant, e.g. "critical"
+fn label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn host_name() -> String {
+    System::host_name().unwrap_or_else(|| "unknown host".to_string())
+}
+
+/// Fills a JSON template's placeholders, escaping the values for use inside strings
+pub(crate) fn render_template(
+    template: &str,
+    event: &WebhookEvent,
+) -> Result<serde_json::Value, serde_json::Error> {
+    let mut body = template.to_string();
+    for (name, value) in [
+        ("title", event.title.clone()),
+        ("message", event.message.clone()),
+        ("severity", label(&event.severity)),
+        ("category", label(&event.category)),
+        ("timestamp", event.timestamp.clone()),
+        ("host", host_name()),
+    ] {
+        let quoted = serde_json::to_string(&value)?;
+        body = body.replace(&format!("{{{{{}}}}}", name), &quoted[1..quoted.len() - 1]);
+    }
+    serde_json::from_str(&body)
+}
+
+fn render_body(
+    target: &WebhookTarget,
+    event: &WebhookEvent,
+) -> Result<serde_json::Value, serde_json::Error> {
+    if let Some(template) = &target.template {
+        return render_template(template, event);
+    }
+
+    let host = host_name();
+    let severity = label(&event.severity);
+    Ok(match target.format {
+        WebhookFormat::Generic => json!({
+            "source": "desktop-agent",
+            "host": host,
+            "category": event.category,
+            "severity": event.severity,
+            "title": event.title,
+            "message": event.message,
+            "timestamp": event.timestamp,
+        }),
+        WebhookFormat::Slack => {
+            let icon = match event.severity {
+                AlertSeverity::Info => ":information_source:",
+                AlertSeverity::Warning => ":warning:",
+                AlertSeverity::Critical => ":red_circle:",
+            };
+            json!({
+                "text": format!("{} *{}* on `{}`\n{}", icon, event.title, host, event.message),
+            })
+        }
+        WebhookFormat::Teams => {
+            let color = match event.severity {
+                AlertSeverity::Info => "2EA043",
+                AlertSeverity::Warning => "DBAB09",
+                AlertSeverity::Critical => "DA3633",
+            };
+            json!({
+                "@type": "MessageCard",
+                "@context": "https://schema.org/extensions",
+                "summary": event.title,
+                "themeColor": color,
+                "title": event.title,
+                "text": event.message,
+                "sections": [{
+                    "facts": [
+                        { "name": "Host", "value": host },
+                        { "name": "Severity", "value": severity },
+                        { "name": "Time", "value": event.timestamp },
+                    ],
+                }],
+            })
+        }
+    })
+}
+
+fn sign(secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let mut context = hmac::Context::with_key(&key);
+    context.update(timestamp.as_bytes());
+    context.update(b".");
+    context.update(body);
+    format!("sha256={}", hex::encode(context.sign().as_ref()))
+}
+
+// Server errors, throttling and timeouts may pass; other client errors will not
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+}
+
+/// Posts an event to one webhook, retrying with exponential backoff
+async fn deliver(
+    client: &reqwest::Client,
+    target: &WebhookTarget,
+    event: &WebhookEvent,
+    settings: &WebhookSettings,
+) -> WebhookDelivery {
+    let mut delivery = WebhookDelivery {
+        webhook: target.name.clone(),
+        title: event.title.clone(),
+        success: false,
+        status: None,
+        attempts: 0,
+        error: None,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+    let body = match render_body(target, event).and_then(|body| serde_json::to_vec(&body)) {
+        Ok(body) => body,
+        Err(error) => {
+            delivery.error = Some(format!("Could not render body: {}", error));
+            return delivery;
+        }
+    };
+
+    let mut backoff = Duration::from_secs(settings.initial_backoff_secs);
+    while delivery.attempts < settings.max_attempts {
+        delivery.attempts += 1;
+        let mut request = client
+            .post(&target.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.clone());
+        if let Some(secret) = &target.secret {
+            let timestamp = chrono::Utc::now().timestamp().to_string();
+            request = request
+                .header(SIGNATURE_HEADER, sign(secret, &timestamp, &body))
+                .header(TIMESTAMP_HEADER, timestamp);
+        }
+
+        let retryable = match request.send().await {
+            Ok(response) => {
+                let status = response.status();
+                delivery.status = Some(status.as_u16());
+                if status.is_success() {
+                    delivery.success = true;
+                    delivery.error = None;
+                    return delivery;
+                }
+                delivery.error = Some(format!("Webhook answered {}", status));
+                is_retryable(status)
+            }
+            Err(error) => {
+                delivery.status = None;
+                delivery.error = Some(error.to_string());
+                true
+            }
+        };
+        if !retryable || delivery.attempts >= settings.max_attempts {
+            break;
+        }
+        tracing::debug!(
+            webhook = %target.name,
+            attempt = delivery.attempts,
+            error = ?delivery.error,
+            "Webhook delivery failed; retrying"
+        );
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+    delivery
+}
+
+/// Sends an event to every enabled webhook subscribed to its category, in the background
+pub(crate) fn publish(event: WebhookEvent) {
+    let settings = config::current_config().webhooks;
+    let targets: Vec<WebhookTarget> = settings
+        .targets
+        .iter()
+        .filter(|target| {
+            target.enabled
+                && (target.categories.is_empty() || target.categories.contains(&event.category))
+        })
+        .cloned()
+        .collect();
+    if targets.is_empty() {
+        return;
+    }
+
+    let client = docker::get_http_client();
+    for target in targets {
+        let client = client.clone();
+        let event = event.clone();
+        let settings = settings.clone();
+        tauri::async_runtime::spawn(async move {
+            let delivery = deliver(&client, &target, &event, &settings).await;
+            if !delivery.success {
+                tracing::warn!(
+                    webhook = %target.name,
+                    attempts = delivery.attempts,
+                    error = ?delivery.error,
+                    "Webhook delivery failed"
+                );
+            }
+            record_delivery(delivery);
+        });
+    }
+}
+
+/// Tauri command to send a test event to one webhook, even if it is disabled
+/// or not subscribed to alerts, and wait for the outcome
+#[tauri::command]
+pub async fn test_webhook(name: String) -> Result<WebhookDelivery, WebhookError> {
+    let settings = config::current_config().webhooks;
+    let target = settings
+        .targets
+        .iter()
+        .find(|target| target.name == name)
+        .cloned()
+        .ok_or(WebhookError::NotFound(name))?;
+
+    let event = WebhookEvent::new(
+        NotificationCategory::Alert,
+        AlertSeverity::Info,
+        "Test notification",
+        "This is a test message from Desktop Agent",
+    );
+    let delivery = deliver(&docker::get_http_client(), &target, &event, &settings).await;
+    record_delivery(delivery.clone());
+    Ok(delivery)
+}
+
+/// Tauri command to get recent webhook deliveries, most recent first
+#[tauri::command]
+pub async fn get_webhook_deliveries(limit: Option<usize>) -> Vec<WebhookDelivery> {
+    let deliveries = get_deliveries();
+    let deliveries_guard = deliveries.lock().unwrap();
+    deliveries_guard
+        .iter()
+        .rev()
+        .take(limit.unwrap_or(MAX_DELIVERIES))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::test_support::TestServer;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn target(url: &str, format: WebhookFormat) -> WebhookTarget {
+        WebhookTarget {
+            name: "test".to_string(),
+            url: url.to_string(),
+            enabled: true,
+            format,
+            categories: Vec::new(),
+            template: None,
+            secret: None,
+        }
+    }
+
+    fn settings(max_attempts: u32) -> WebhookSettings {
+        WebhookSettings {
+            targets: Vec::new(),
+            max_attempts,
+            initial_backoff_secs: 0,
+        }
+    }
+
+    fn event() -> WebhookEvent {
+        WebhookEvent::new(
+            NotificationCategory::Alert,
+            AlertSeverity::Critical,
+            "Disk \"/\" full",
+            "Usage is 97%\nClean up images",
+        )
+    }
+
+    // Answers each request with the next status in turn, repeating the last one
+    async fn server_answering(statuses: &'static [u16]) -> TestServer {
+        let count = AtomicUsize::new(0);
+        TestServer::start(move |_| {
+            let index = count.fetch_add(1, Ordering::SeqCst).min(statuses.len() - 1);
+            (statuses[index], String::new())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn signs_timestamp_and_body() {
+        let server = server_answering(&[200]).await;
+        let mut target = target(&server.url, WebhookFormat::Generic);
+        target.secret = Some("s3cret".to_string());
+
+        let delivery = deliver(&reqwest::Client::new(), &target, &event(), &settings(1)).await;
+        assert!(delivery.success);
+
+        let request = &server.requests()[0];
+        let timestamp = request.header(TIMESTAMP_HEADER).unwrap();
+        let signature = request.header(SIGNATURE_HEADER).unwrap();
+        let signature = hex::decode(signature.strip_prefix("sha256=").unwrap()).unwrap();
+        let mut signed = format!("{}.", timestamp).into_bytes();
+        signed.extend_from_slice(&request.body);
+        let key = hmac::Key::new(hmac::HMAC_SHA256, b"s3cret");
+        assert!(hmac::verify(&key, &signed, &signature).is_ok());
+        assert_eq!(request.json()["title"], "Disk \"/\" full");
+    }
+
+    #[tokio::test]
+    async fn unsigned_without_secret() {
+        let server = server_answering(&[200]).await;
+        let target = target(&server.url, WebhookFormat::Generic);
+        deliver(&reqwest::Client::new(), &target, &event(), &settings(1)).await;
+
+        let request = &server.requests()[0];
+        assert_eq!(request.header(SIGNATURE_HEADER), None);
+        assert_eq!(request.header(TIMESTAMP_HEADER), None);
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_and_throttling() {
+        let server = server_answering(&[503, 429, 200]).await;
+        let target = target(&server.url, WebhookFormat::Generic);
+
+        let delivery = deliver(&reqwest::Client::new(), &target, &event(), &settings(4)).await;
+        assert!(delivery.success);
+        assert_eq!(delivery.attempts, 3);
+        assert_eq!(delivery.status, Some(200));
+        assert_eq!(delivery.error, None);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_client_errors() {
+        let server = server_answering(&[404, 200]).await;
+        let target = target(&server.url, WebhookFormat::Generic);
+
+        let delivery = deliver(&reqwest::Client::new(), &target, &event(), &settings(4)).await;
+        assert!(!delivery.success);
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.status, Some(404));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn stops_after_max_attempts() {
+        let server = server_answering(&[500]).await;
+        let target = target(&server.url, WebhookFormat::Generic);
+
+        let delivery = deliver(&reqwest::Client::new(), &target, &event(), &settings(3)).await;
+        assert!(!delivery.success);
+        assert_eq!(delivery.attempts, 3);
+        assert_eq!(delivery.status, Some(500));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[test]
+    fn slack_body_is_a_message() {
+        let body = render_body(&target("", WebhookFormat::Slack), &event()).unwrap();
+        let text = body["text"].as_str().unwrap();
+        assert!(text.starts_with(":red_circle: *Disk \"/\" full* on `"));
+        assert!(text.ends_with("\nUsage is 97%\nClean up images"));
+    }
+
+    #[test]
+    fn teams_body_is_a_message_card() {
+        let body = render_body(&target("", WebhookFormat::Teams), &event()).unwrap();
+        assert_eq!(body["@type"], "MessageCard");
+        assert_eq!(body["themeColor"], "DA3633");
+        assert_eq!(body["title"], "Disk \"/\" full");
+        assert_eq!(body["text"], "Usage is 97%\nClean up images");
+        assert_eq!(body["sections"][0]["facts"][1]["value"], "critical");
+    }
+
+    #[test]
+    fn template_escapes_values() {
+        let template = r#"{"summary": "[{{severity}}] {{title}}", "details": "{{message}}", "kind": "{{category}}"}"#;
+        let body = render_template(template, &event()).unwrap();
+        assert_eq!(body["summary"], "[critical] Disk \"/\" full");
+        assert_eq!(body["details"], "Usage is 97%\nClean up images");
+        assert_eq!(body["kind"], "alert");
+    }
+
+    #[test]
+    fn template_must_be_json() {
+        assert!(render_template("{{title}}", &event()).is_err());
+    }
+}
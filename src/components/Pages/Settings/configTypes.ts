@@ -0,0 +1,177 @@
+// TypeScript types matching the Rust backend AgentConfig structure, read with
+// `get_config` and saved with `update_config`.
+
+export type UpdateChannel = "stable" | "test";
+
+export type UpdateSource =
+  | { provider: "docker_hub"; base_url: string; repository: string }
+  | { provider: "github_releases"; api_url: string; repository: string }
+  | { provider: "json_feed"; url: string }
+  | { provider: "desktop_appcast"; base_url: string };
+
+export interface MonitoringSettings {
+  retry_interval_secs: number;
+  health_check_interval_secs: number;
+  update_check_interval_secs: number;
+  connection_timeout_secs: number;
+  max_retries: number;
+  stats_interval_secs: number;
+  restart_alert_threshold: number;
+  restart_alert_window_secs: number;
+}
+
+export interface UpdateSettings {
+  engine_source: UpdateSource;
+  desktop_source: UpdateSource;
+  channel: UpdateChannel;
+}
+
+export interface HttpSettings {
+  timeout_secs: number;
+  user_agent: string;
+}
+
+export type LogLevel = "error" | "warn" | "info" | "debug" | "trace";
+
+export interface LoggingSettings {
+  level: LogLevel;
+}
+
+export interface MetricsSettings {
+  sample_interval_secs: number;
+  raw_retention_hours: number;
+  minute_retention_days: number;
+  hour_retention_days: number;
+}
+
+export interface PrometheusSettings {
+  enabled: boolean;
+  bind_address: string;
+  port: number;
+}
+
+export type AlertSeverity = "info" | "warning" | "critical";
+
+export type AlertCondition =
+  | {
+      kind: "metric";
+      metric: string;
+      comparison: "above" | "below";
+      threshold: number;
+    }
+  | { kind: "docker_not_running"; endpoint?: string | null }
+  | { kind: "container_not_running"; container: string };
+
+export interface AlertRule {
+  id: string;
+  name: string;
+  enabled: boolean;
+  severity: AlertSeverity;
+  condition: AlertCondition;
+  for_secs: number;
+  hysteresis: number;
+}
+
+export interface AlertSettings {
+  rules: AlertRule[];
+}
+
+// Payload of `alert-fired`/`alert-resolved` and of `get_active_alerts`/`get_alert_history`
+export interface Alert {
+  key: string;
+  rule_id: string;
+  rule_name: string;
+  severity: AlertSeverity;
+  subject: string;
+  message: string;
+  value: number | null;
+  fired_at: string;
+  resolved_at: string | null;
+}
+
+export type NotificationCategory =
+  | "docker_state"
+  | "update_available"
+  | "container_crashed"
+  | "alert";
+
+export interface NotificationSettings {
+  enabled: boolean;
+  muted: NotificationCategory[];
+  cooldown_secs: number;
+  max_per_minute: number;
+  test_mode: boolean;
+}
+
+// Returned by `get_recorded_notifications` while `test_mode` is on
+export interface Notification {
+  category: NotificationCategory;
+  title: string;
+  body: string;
+  timestamp: string;
+}
+
+export type WebhookFormat = "generic" | "slack" | "teams";
+
+export interface WebhookTarget {
+  name: string;
+  url: string;
+  enabled: boolean;
+  format: WebhookFormat;
+  // Empty means every category
+  categories: NotificationCategory[];
+  // JSON with {{title}}, {{message}}, {{severity}}, {{category}}, {{timestamp}} and {{host}}
+  template?: string | null;
+  secret?: string | null;
+}
+
+export interface WebhookSettings {
+  targets: WebhookTarget[];
+  max_attempts: number;
+  initial_backoff_secs: number;
+}
+
+// Returned by `test_webhook` and `get_webhook_deliveries`
+export interface WebhookDelivery {
+  webhook: string;
+  title: string;
+  success: boolean;
+  status: number | null;
+  attempts: number;
+  error: string | null;
+  timestamp: string;
+}
+
+export interface TraySettings {
+  close_to_tray: boolean;
+  pinned_container?: string | null;
+}
+
+export interface AgentConfig {
+  monitoring: MonitoringSettings;
+  updates: UpdateSettings;
+  http: HttpSettings;
+  logging: LoggingSettings;
+  metrics: MetricsSettings;
+  prometheus: PrometheusSettings;
+  alerts: AlertSettings;
+  notifications: NotificationSettings;
+  tray: TraySettings;
+  webhooks: WebhookSettings;
+}
+
+// An entry returned by `get_recent_logs`
+export interface LogEntry {
+  timestamp: string;
+  level: LogLevel;
+  target: string;
+  spans: string;
+  message: string;
+  fields: Record<string, string>;
+}
+
+// Errors returned by `update_config`, serialized from the Rust ConfigError enum
+export type ConfigError =
+  | { Io: string }
+  | { Parse: string }
+  | { Invalid: { field: string; reason: string } };
@@ -10,11 +10,34 @@ export interface OperatingSystemInfo {
   uptime: number;
 }
 
+export interface CpuCoreInfo {
+  name: string;
+  usage_percent: number | null;
+  frequency: number;
+}
+
+export interface LoadAverage {
+  one: number;
+  five: number;
+  fifteen: number;
+}
+
+export interface CpuCache {
+  level: number;
+  kind: string; // "data", "instruction" or "unified"
+  size_kb: number;
+}
+
 export interface CpuInfo {
   brand: string;
-  frequency: number;
+  vendor: string;
+  frequency: number; // average across cores, MHz
   physical_cores: number;
   logical_cores: number;
+  usage_percent: number | null; // null until the sampler has measured it
+  cores: CpuCoreInfo[];
+  load_average: LoadAverage | null; // not available on Windows
+  caches: CpuCache[];
 }
 
 export interface GpuInfo {
@@ -37,6 +60,7 @@ export interface StorageDevice {
   used_size: number;
   available_size: number;
   unit: string; // "GB" or "TB"
+  mount_point: string;
 }
 
 export interface StorageInfo {
@@ -52,6 +76,16 @@ export interface PerformanceInfo {
 }
 
 // Rust error types that match the backend
+// History returned by `query_metrics`; timestamps are milliseconds since the epoch
+export type MetricResolution = "raw" | "minute" | "hour";
+
+export interface MetricPoint {
+  timestamp: number;
+  value: number;
+  min: number;
+  max: number;
+}
+
 export type SysInfoError = 
   | { System: string }
   | { Nvml: string };
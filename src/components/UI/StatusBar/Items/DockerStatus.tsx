@@ -10,12 +10,24 @@ interface DockerVersion {
   arch: string;
 }
 
+interface UpdateInfo {
+  current_version: string;
+  latest_version: string;
+  update_available: boolean;
+  release_notes: string | null;
+}
+
 interface DockerStatus {
+  endpoint: string;
   is_running: boolean;
+  host: string | null;
+  engine_flavor: "docker" | "docker_rootless" | "docker_desktop" | "podman" | null;
   engine_version: DockerVersion | null;
   desktop_version: string | null;
   engine_update_available: boolean | null;
   desktop_update_available: boolean | null;
+  engine_update: UpdateInfo | null;
+  desktop_update: UpdateInfo | null;
   error: string | null;
   container_count: number | null;
   last_checked: string | null;
@@ -23,11 +35,16 @@ interface DockerStatus {
 
 const DockerStatus: React.FC = () => {
   const [status, setStatus] = useState<DockerStatus>({
+    endpoint: "default",
     is_running: false,
+    host: null,
+    engine_flavor: null,
     engine_version: null,
     desktop_version: null,
     engine_update_available: null,
     desktop_update_available: null,
+    engine_update: null,
+    desktop_update: null,
     error: "Initializing...",
     container_count: null,
     last_checked: null,